@@ -1,37 +1,75 @@
 use std::error;
-use mysql::*;
-use mysql::prelude::*;
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 use structopt::StructOpt;
 
+use crate::config::{Config, Profile};
+use crate::database::{self, Cancel, Connection, Database, Fetch, QueryResult, Sink, TableInfo, Value};
+use crate::edit::Edits;
+use crate::event::Event;
+use crate::export::{Export, Format};
+use crate::sql::{self, Statement};
+
 pub type AppResult<T>= 
 std::result::Result<T,Box<dyn error::Error>>;
 
+pub fn error_chain(err: &dyn error::Error)-> String{
+    let mut chain= err.to_string();
+    let mut source= err.source();
+    while let Some(err)= source{
+        chain.push_str(&format!(": {}",err));
+        source= err.source();
+    }
+    chain
+}
+
 pub enum AppMode{
     Normal,
     Editor,
     Message,
     Table,
-    Login
+    Login,
+    Profile,
+    Browser,
+    Inspector,
+    Detail,
+    Filter,
+    Export,
+    Edit,
+    Preview
 }
 
 pub struct App{
     pub state: bool,
     pub mode: AppMode,
-    pub editor: Editor,
-    pub message: Message,
-    pub table: Table,
-    pub info: DatabaseInfo
+    pub login: Login,
+    pub profiles: Vec<(String,Profile)>,
+    pub profile_index: usize,
+    pub defaults: DatabaseInfo,
+    pub sessions: Vec<Session>,
+    pub current: usize,
+    pub prompt: String,
+    pub next_id: usize,
+    pub sender: Option<mpsc::Sender<Event>>
 }
 
 impl Default for App {
     fn default() -> Self {
+        let info= DatabaseInfo::load();
         Self {
             state: true,
             mode: AppMode::Normal,
-            editor: Editor::new(),
-            message: Message::new(),
-            table: Table::new(),
-            info: DatabaseInfo::from_args()
+            login: Login::from_info(&info),
+            profiles: Vec::new(),
+            profile_index: 0,
+            defaults: info.clone(),
+            sessions: vec![Session::new(0,info)],
+            current: 0,
+            prompt: String::new(),
+            next_id: 1,
+            sender: None
         }
     }
 }
@@ -54,40 +92,787 @@ impl App {
     pub fn change_mode(&mut self,mode: AppMode) {
         self.mode= mode; 
     }
+    pub fn session(&mut self)-> &mut Session {
+        &mut self.sessions[self.current]
+    }
+    fn find_session(&mut self,id: usize)-> Option<&mut Session> {
+        self.sessions.iter_mut().find(|session| session.id== id)
+    }
+    pub fn load_config(&mut self)-> AppResult<()> {
+        let config= Config::load()?;
+        let info= &mut self.sessions[self.current].info;
+        info.apply_url()?;
+        if let Some(name)= info.profile.clone(){
+            match config.profiles.get(&name){
+                Some(profile)=> profile.apply(info)?,
+                None=> return Err(format!("no profile named '{}' in {}",name,
+                        Config::path().unwrap_or_default().display()).into())
+            }
+        }
+        self.profiles= config.profiles.into_iter().collect();
+        if std::env::args_os().len()== 1&& !self.profiles.is_empty(){
+            self.mode= AppMode::Profile;
+        }else {
+            info.apply_credentials()?;
+        }
+        self.login= Login::from_info(info);
+        Ok(())
+    }
+    pub fn start(&mut self) {
+        if self.session().info.need_password(){
+            self.mode= AppMode::Login;
+        }else {
+            self.mode= AppMode::Normal;
+            self.session().connect();
+        }
+    }
+    pub fn next_profile(&mut self) {
+        if self.profile_index+ 1< self.profiles.len(){
+            self.profile_index+= 1;
+        }
+    }
+    pub fn prev_profile(&mut self) {
+        self.profile_index= self.profile_index.saturating_sub(1);
+    }
+    pub fn pick_profile(&mut self) {
+        let session= &mut self.sessions[self.current];
+        if let Some((name,profile))= self.profiles.get(self.profile_index){
+            session.info.profile= Some(name.clone());
+            if let Err(err)= profile.apply(&mut session.info){
+                session.message.push(format!("{}\n",err));
+            }
+        }
+        self.skip_profiles();
+    }
+    pub fn skip_profiles(&mut self) {
+        let session= self.session();
+        if let Err(err)= session.info.apply_credentials(){
+            session.message.push(format!("{}\n",err));
+        }
+        self.login= Login::from_info(&self.session().info);
+        self.start();
+    }
+    pub fn new_session(&mut self) {
+        let mut session= Session::new(self.next_id,self.defaults.clone());
+        if let Err(err)= session.info.apply_url(){
+            session.message.push(format!("{:?}\n",err));
+        }
+        if self.profiles.is_empty(){
+            if let Err(err)= session.info.apply_credentials(){
+                session.message.push(format!("{}\n",err));
+            }
+        }
+        self.next_id+= 1;
+        self.login= Login::from_info(&session.info);
+        self.sessions.push(session);
+        self.current= self.sessions.len()- 1;
+        match self.profiles.is_empty(){
+            true=> self.mode= AppMode::Login,
+            false=> {
+                self.profile_index= 0;
+                self.mode= AppMode::Profile;
+            }
+        }
+    }
+    pub fn close_session(&mut self) {
+        if self.sessions.len()== 1{
+            self.session().message.push(String::from("can not close the last session\n"));
+            return
+        }
+        let session= self.sessions.remove(self.current);
+        if session.running.is_some(){
+            session.cancel_query();
+        }
+        self.current= self.current.min(self.sessions.len()- 1);
+        self.mode= AppMode::Normal;
+    }
+    pub fn next_session(&mut self) {
+        self.current= (self.current+ 1)% self.sessions.len();
+    }
+    pub fn prev_session(&mut self) {
+        self.current= (self.current+ self.sessions.len()- 1)% self.sessions.len();
+    }
+    pub fn cancel_login(&mut self) {
+        match self.sessions.len(){
+            1=> self.quit(),
+            _=> self.close_session()
+        }
+    }
+    pub fn login(&mut self) {
+        let session= &mut self.sessions[self.current];
+        if let Err(err)= self.login.apply(&mut session.info){
+            self.login.notice= Some(Err(err));
+            return;
+        }
+        match session.open(){
+            Ok(())=> {
+                self.login.notice= None;
+                self.mode= AppMode::Normal;
+            },
+            Err(err)=> self.login.notice= Some(Err(error_chain(err.as_ref())))
+        }
+    }
+    pub fn test_login(&mut self) {
+        let mut info= self.session().info.clone();
+        if let Err(err)= self.login.apply(&mut info){
+            self.login.notice= Some(Err(err));
+            return;
+        }
+        self.login.notice= Some(match database::connect(&info){
+            Ok(_)=> Ok(format!("connected to {}",info.address())),
+            Err(err)=> Err(error_chain(err.as_ref()))
+        });
+    }
     pub fn send_query(&mut self) {
-        let pool= match Pool::new(self.info.build_opts()){
-            Ok(pool)=> pool,
-            Err(err)=> {
-                self.message.push(format!("{:?}\n",err));
+        let session= self.session();
+        let statements= sql::split(&session.editor.context.concat(),session.info.engine());
+        self.run(statements);
+    }
+    pub fn send_current(&mut self) {
+        let session= self.session();
+        let editor= &session.editor;
+        let text= editor.context.concat();
+        let offset= editor.offset(editor.line_index,editor.cursor_index);
+        match sql::statement_at(&text,offset,session.info.engine()){
+            Some(statement)=> self.run(vec![statement]),
+            None=> self.session().message.push(String::from("no statement under the cursor\n"))
+        }
+    }
+    pub fn send_selection(&mut self) {
+        let engine= self.session().info.engine();
+        match self.session().editor.selected_text(){
+            Some(text)=> self.run(sql::split(&text,engine)),
+            None=> self.session().message.push(String::from("nothing selected\n"))
+        }
+    }
+    fn run(&mut self,statements: Vec<Statement>) {
+        let session= &mut self.sessions[self.current];
+        if session.running.is_some(){
+            session.message.push(String::from("a query is already running\n"));
+            return
+        }
+        if session.fetch.take().is_some(){
+            session.stopping= true;
+        }
+        if !session.check_connection(){
+            return
+        }
+        let (conn,sender)= match (&session.conn,&self.sender){
+            (Some(conn),Some(sender))=> (Arc::clone(conn),sender.clone()),
+            _=> return
+        };
+        let id= session.id;
+        let max= session.info.max_rows.max(1);
+        let (requests,receiver)= mpsc::channel();
+        session.fetch= Some(requests);
+        session.running= Some(Instant::now());
+        session.results.start_batch();
+        thread::spawn(move || {
+            let mut conn= conn.lock().expect("connection poisoned");
+            let last= statements.len().saturating_sub(1);
+            for (index,statement) in statements.iter().enumerate(){
+                let mut pager= Pager::new(id,&sender,&statement.text,max);
+                if index== last{
+                    pager.requests= Some(&receiver);
+                }
+                let result= conn.query(&statement.text,&mut pager);
+                match result{
+                    Ok(result)=> {
+                        if pager.finish(result).is_err(){
+                            return
+                        }
+                    },
+                    Err(err)=> {
+                        if pager.flushed{
+                            pager.fetch= Fetch::Stopped;
+                            let _= pager.flush();
+                        }
+                        let _= sender.send(Event::QueryError(id,format!("{:?}\n",err)));
+                        break
+                    }
+                };
+            }
+            let _= sender.send(Event::QueryDone(id));
+            drop(conn);
+        });
+    }
+    pub fn open_browser(&mut self) {
+        self.mode= AppMode::Browser;
+        let session= self.session();
+        if session.browser.nodes.is_none(){
+            session.browse(|browser,db| browser.reload(db));
+        }
+    }
+    pub fn browser_select(&mut self) {
+        let session= self.session();
+        let statement= session.idle()
+            .and_then(|conn| conn.try_lock().ok()
+                      .and_then(|conn| session.browser.select_statement(conn.as_ref())));
+        match statement{
+            Some(statement)=> {
+                session.editor.append_line(&statement);
+                self.mode= AppMode::Editor;
+            },
+            None=> session.browse(|browser,db| browser.expand(db,false))
+        }
+    }
+    pub fn inspect_table(&mut self) {
+        let session= self.session();
+        let (schema,table)= match session.browser.selected_table(){
+            Some(table)=> table,
+            None=> return
+        };
+        let mut info= None;
+        session.browse(|_,db| {
+            info= Some(db.describe_table(&schema,&table)?);
+            Ok(())
+        });
+        if let Some(info)= info{
+            session.inspector= Some(Panel::table(&info));
+            self.mode= AppMode::Inspector;
+        }
+    }
+    pub fn open_filter(&mut self) {
+        self.prompt= self.session().results.current().filter.clone().unwrap_or_default();
+        self.mode= AppMode::Filter;
+    }
+    pub fn apply_filter(&mut self) {
+        let prompt= self.prompt.clone();
+        self.session().results.current().set_filter(&prompt);
+        self.mode= AppMode::Table;
+        self.fetch_more();
+    }
+    pub fn open_export(&mut self) {
+        let table= self.session().results.current();
+        if table.headers.is_empty(){
+            return;
+        }
+        let name= sql::source_table(&table.query).map_or_else(|| String::from("result"),|(_,name)| name);
+        self.prompt= format!("{}.csv",name);
+        self.mode= AppMode::Export;
+    }
+    pub fn apply_export(&mut self) {
+        let path= match (self.prompt.trim().strip_prefix("~/"),dirs::home_dir()){
+            (Some(rest),Some(home))=> home.join(rest),
+            _=> PathBuf::from(self.prompt.trim())
+        };
+        self.mode= AppMode::Table;
+        let session= self.session();
+        let format= match Format::from_path(&path){
+            Some(format)=> format,
+            None=> {
+                session.message.push(format!("{}: unknown export format, use .csv, .tsv, .json, .md or .sql\n",
+                                             path.display()));
                 return
             }
         };
-        let mut conn= match pool.get_conn() {
+        let engine= session.info.engine();
+        let table= &session.results.tables[session.results.index];
+        let quote= |name: &str| match format{
+            Format::Sql=> engine.quote_identifier(name),
+            _=> name.to_string()
+        };
+        let name= match sql::source_table(&table.query){
+            Some((Some(schema),name))=> format!("{}.{}",quote(&schema),quote(&name)),
+            Some((None,name))=> quote(&name),
+            None=> quote(&path.file_stem().unwrap_or_default().to_string_lossy())
+        };
+        let export= Export{
+            format,
+            headers: &table.headers,
+            rows: table.rows().map(Vec::as_slice).collect(),
+            table: name,
+            engine,
+            quote: &quote
+        };
+        let count= export.rows.len();
+        match export.write(&path){
+            Ok(())=> session.message.push(format!("exported {} rows to {}\n",count,path.display())),
+            Err(err)=> session.message.push(format!("{:?}\n",err))
+        }
+    }
+    fn start_editing(&mut self)-> bool{
+        let session= self.session();
+        let table= session.results.current();
+        if table.edits.is_some(){
+            return true;
+        }
+        if table.headers.is_empty(){
+            return false;
+        }
+        let conn= match session.idle(){
+            Some(conn)=> conn,
+            None=> return false
+        };
+        let table= &mut session.results.tables[session.results.index];
+        let (schema,name)= match sql::editable_table(&table.query){
+            Some(source)=> source,
+            None=> {
+                session.message.push(String::from("not editable: the result is not plain columns of a single table\n"));
+                return false
+            }
+        };
+        let mut conn= match conn.try_lock(){
             Ok(conn)=> conn,
+            Err(_)=> {
+                session.message.push(String::from("connection is busy, try again when the query is done\n"));
+                return false
+            }
+        };
+        let db= conn.as_mut();
+        let engine= session.info.engine();
+        let edits= (|| -> AppResult<Edits> {
+            let schema= match schema{
+                Some(schema)=> schema,
+                None=> db.current_schema()?
+            };
+            let info= db.describe_table(&schema,&name)?;
+            if let Some(header)= table.headers
+                .iter()
+                .find(|header| !info.columns.iter().any(|column| column.name== **header)){
+                return Err(format!("not editable: {} is not a column of {}",header,name).into());
+            }
+            let key= info.primary_key();
+            if key.is_empty(){
+                return Err(format!("not editable: {} has no primary key",name).into());
+            }
+            let key= key
+                .iter()
+                .map(|column| table.headers
+                     .iter()
+                     .position(|header| header== column)
+                     .ok_or_else(|| format!("not editable: primary key column {} is not in the result",column)))
+                .collect::<Result<Vec<usize>,String>>()?;
+            Ok(Edits::new(engine,format!("{}.{}",db.quote_identifier(&schema),db.quote_identifier(&name)),
+                          table.headers.iter().map(|header| db.quote_identifier(header)).collect(),
+                          key))
+        })();
+        match edits{
+            Ok(edits)=> {
+                table.edits= Some(edits);
+                true
+            },
             Err(err)=> {
-                self.message.push(format!("{:?}\n",err));
+                session.message.push(format!("{}\n",err));
+                false
+            }
+        }
+    }
+    pub fn open_edit(&mut self) {
+        if !self.start_editing(){
+            return;
+        }
+        self.prompt= match self.session().results.current().selected(){
+            Some(Value::Null)| None=> String::new(),
+            Some(value)=> value.display()
+        };
+        self.mode= AppMode::Edit;
+    }
+    pub fn apply_edit(&mut self) {
+        let prompt= self.prompt.clone();
+        let table= self.session().results.current();
+        let value= table.template().parse_like(&prompt);
+        table.set_cell(value);
+        self.mode= AppMode::Table;
+    }
+    pub fn set_null(&mut self) {
+        if self.start_editing(){
+            self.session().results.current().set_cell(Value::Null);
+        }
+    }
+    pub fn delete_row(&mut self) {
+        if self.start_editing(){
+            self.session().results.current().toggle_delete();
+        }
+    }
+    pub fn add_row(&mut self) {
+        if self.start_editing(){
+            self.session().results.current().add_row();
+        }
+    }
+    pub fn revert_row(&mut self) {
+        self.session().results.current().revert_row();
+    }
+    pub fn preview_edits(&mut self) {
+        let session= self.session();
+        let table= session.results.current();
+        let statements= match &table.edits{
+            Some(edits)=> edits.statements(&table.items),
+            None=> Vec::new()
+        };
+        if statements.is_empty(){
+            session.message.push(String::from("no changes to commit\n"));
+            return;
+        }
+        session.detail= Some(Panel{
+            title: format!("{} statements",statements.len()),
+            lines: statements,
+            wrap: true,
+            size: (0,0),
+            scroll: (0,0)
+        });
+        self.mode= AppMode::Preview;
+    }
+    pub fn commit_edits(&mut self) {
+        self.mode= AppMode::Table;
+        let session= self.session();
+        let conn= match session.idle(){
+            Some(conn)=> conn,
+            None=> return
+        };
+        let table= &mut session.results.tables[session.results.index];
+        let statements= match &table.edits{
+            Some(edits)=> edits.statements(&table.items),
+            None=> return
+        };
+        let mut conn= match conn.try_lock(){
+            Ok(conn)=> conn,
+            Err(_)=> {
+                session.message.push(String::from("connection is busy, try again when the query is done\n"));
                 return
             }
         };
-        let querys= self.editor.context.concat();
+        match database::transaction(conn.as_mut(),&statements){
+            Ok(())=> {
+                table.finish_edits();
+                session.message.push(format!("committed {} statements\n",statements.len()));
+            },
+            Err(err)=> session.message.push(format!("{:?}, rolled back\n",err))
+        }
+    }
+    pub fn open_detail(&mut self) {
+        let session= self.session();
+        let table= session.results.current();
+        let value= match table.selected(){
+            Some(value)=> value.clone(),
+            None=> return
+        };
+        let title= format!("{} {} (row {})",table.headers[table.cursor.1],
+                           table.types.get(table.cursor.1).cloned().unwrap_or_default(),
+                           table.cursor.0+ 1);
+        session.detail= Some(Panel::cell(title,&value));
+        self.mode= AppMode::Detail;
+    }
+    pub fn cancel_query(&mut self) {
+        let session= self.session();
+        if let Some(cancel)= &session.cancel{
+            match cancel.cancel(){
+                Ok(())=> session.message.push(String::from("cancel requested\n")),
+                Err(err)=> session.message.push(format!("{:?}\n",err))
+            }
+        }
+    }
+    pub fn query_result(&mut self,id: usize,result: QueryResult) {
+        if let Some(session)= self.find_session(id){
+            session.message.push(result.summary());
+            session.results.push(result);
+        }
+    }
+    pub fn query_error(&mut self,id: usize,err: String) {
+        if let Some(session)= self.find_session(id){
+            session.message.push(err);
+        }
+    }
+    pub fn query_rows(&mut self,id: usize,rows: Vec<Vec<Value>>,fetch: Fetch) {
+        if let Some(session)= self.find_session(id){
+            session.results.append(rows,fetch);
+        }
+        if self.sessions[self.current].id== id{
+            self.fetch_more();
+        }
+    }
+    pub fn query_finished(&mut self,id: usize,mut result: QueryResult) {
+        if let Some(session)= self.find_session(id){
+            session.results.append(std::mem::take(&mut result.rows),result.fetch);
+            let count= session.results.tables.last().map_or(0,|table| table.items.len());
+            session.message.push(result.summary_for(count));
+        }
+    }
+    pub fn query_paused(&mut self,id: usize) {
+        if let Some(session)= self.find_session(id){
+            if let Some(start)= session.running.take(){
+                session.message.push(format!("done in {:.3}s, more rows are fetched as you scroll\n",
+                                             start.elapsed().as_secs_f32()));
+            }
+        }
+    }
+    pub fn fetch_more(&mut self) {
+        let session= self.session();
+        if session.results.index+ 1!= session.results.tables.len(){
+            return;
+        }
+        let table= &mut session.results.tables[session.results.index];
+        if table.fetch!= Fetch::More|| table.requested|| table.cursor.0+ table.size.1 as usize* 2< table.view.len(){
+            return;
+        }
+        if let Some(requests)= &session.fetch{
+            table.requested= requests.send(()).is_ok();
+        }
+    }
+    pub fn query_done(&mut self,id: usize) {
+        if let Some(session)= self.find_session(id){
+            if session.stopping{
+                session.stopping= false;
+                return
+            }
+            if let Some(start)= session.running.take(){
+                session.message.push(format!("done in {:.3}s\n",start.elapsed().as_secs_f32()));
+            }
+            session.fetch= None;
+            session.check_connection();
+        }
+    }
+}
+
+const FETCH_ROWS: usize= 500;
+
+struct Pager<'a>{
+    id: usize,
+    sender: &'a mpsc::Sender<Event>,
+    requests: Option<&'a mpsc::Receiver<()>>,
+    query: &'a str,
+    start: Instant,
+    waited: Duration,
+    headers: Vec<String>,
+    types: Vec<String>,
+    page: Vec<Vec<Value>>,
+    fetch: Fetch,
+    fetched: usize,
+    wanted: usize,
+    max: usize,
+    flushed: bool,
+    paused: bool
+}
+
+impl<'a> Pager<'a>{
+    fn new(id: usize,sender: &'a mpsc::Sender<Event>,query: &'a str,max: usize)-> Self{
+        Self{
+            id,
+            sender,
+            requests: None,
+            query,
+            start: Instant::now(),
+            waited: Duration::ZERO,
+            headers: Vec::new(),
+            types: Vec::new(),
+            page: Vec::new(),
+            fetch: Fetch::Done,
+            fetched: 0,
+            wanted: FETCH_ROWS,
+            max,
+            flushed: false,
+            paused: false
+        }
+    }
+    fn send(&self,event: Event)-> AppResult<()>{
+        self.sender.send(event).map_err(|_| "the application has quit".into())
+    }
+    fn flush(&mut self)-> AppResult<()>{
+        let rows= std::mem::take(&mut self.page);
+        let event= match self.flushed{
+            true=> Event::QueryRows(self.id,rows,self.fetch),
+            false=> Event::QueryResult(self.id,QueryResult{
+                query: self.query.to_string(),
+                headers: self.headers.clone(),
+                types: self.types.clone(),
+                rows,
+                fetch: self.fetch,
+                elapsed: self.start.elapsed()- self.waited,
+                ..Default::default()
+            })
+        };
+        self.flushed= true;
+        self.send(event)
+    }
+    fn finish(&mut self,mut result: QueryResult)-> AppResult<()>{
+        if self.fetch== Fetch::More{
+            self.fetch= Fetch::Done;
+        }
+        result.query= self.query.to_string();
+        result.rows= std::mem::take(&mut self.page);
+        result.fetch= self.fetch;
+        result.elapsed= self.start.elapsed()- self.waited;
+        match self.flushed{
+            true=> self.send(Event::QueryFinished(self.id,result)),
+            false=> self.send(Event::QueryResult(self.id,result))
+        }
+    }
+}
 
-        for query in querys.split(';'){
-            if query.len()== 1 {
-                continue;
+impl Sink for Pager<'_>{
+    fn columns(&mut self,headers: &[String],types: &[String])-> AppResult<()>{
+        self.headers= headers.to_vec();
+        self.types= types.to_vec();
+        Ok(())
+    }
+    fn row(&mut self,row: Vec<Value>)-> AppResult<bool>{
+        self.page.push(row);
+        self.fetched+= 1;
+        if self.fetched>= self.max{
+            self.fetch= Fetch::Truncated;
+            return Ok(false);
+        }
+        if self.page.len()< FETCH_ROWS{
+            return Ok(true);
+        }
+        self.fetch= Fetch::More;
+        self.flush()?;
+        let requests= match self.requests{
+            Some(requests) if self.fetched>= self.wanted=> requests,
+            _=> return Ok(true)
+        };
+        if !self.paused{
+            self.paused= true;
+            self.send(Event::QueryPaused(self.id))?;
+        }
+        let waiting= Instant::now();
+        let request= requests.recv();
+        self.waited+= waiting.elapsed();
+        match request{
+            Ok(())=> {
+                self.wanted= self.fetched+ FETCH_ROWS;
+                Ok(true)
+            },
+            Err(_)=> {
+                self.fetch= Fetch::Stopped;
+                Ok(false)
             }
-            let rows: Vec<Row>= match conn.query(query){
-                Ok(rows)=> rows,
-                Err(err)=> {
-                    self.message.push(format!("{:?}\n",err));
-                    return
-                }
-            };
-            self.table.get_table(rows);
         }
     }
 }
 
-#[derive(StructOpt)]
+pub struct Session{
+    pub id: usize,
+    pub info: DatabaseInfo,
+    pub editor: Editor,
+    pub message: Message,
+    pub results: Results,
+    pub browser: Browser,
+    pub inspector: Option<Panel>,
+    pub detail: Option<Panel>,
+    pub conn: Option<Connection>,
+    pub running: Option<Instant>,
+    pub cancel: Option<Box<dyn Cancel>>,
+    pub fetch: Option<mpsc::Sender<()>>,
+    pub stopping: bool,
+    pub encryption: Option<String>
+}
+
+impl Session{
+    pub fn new(id: usize,info: DatabaseInfo)-> Self{
+        Self{
+            id,
+            info,
+            editor: Editor::new(),
+            message: Message::new(),
+            results: Results::new(),
+            browser: Browser::new(),
+            inspector: None,
+            detail: None,
+            conn: None,
+            running: None,
+            cancel: None,
+            fetch: None,
+            stopping: false,
+            encryption: None
+        }
+    }
+    pub fn title(&self)-> String{
+        let name= match (&self.info.profile,self.info.engine()){
+            (Some(profile),_)=> profile.clone(),
+            (None,Engine::Sqlite)=> self.info.sqlite.clone().unwrap_or_default(),
+            (None,_)=> format!("{}@{}",self.info.user,self.info.host)
+        };
+        match (&self.running,&self.conn){
+            (Some(_),_)=> format!("{} *",name),
+            (None,None)=> format!("{} (disconnected)",name),
+            (None,Some(_))=> name
+        }
+    }
+    pub fn connect(&mut self) {
+        if let Err(err)= self.open(){
+            self.message.push(format!("{:?}\n",err));
+        }
+    }
+    fn open(&mut self)-> AppResult<()> {
+        self.conn= None;
+        let conn= database::connect(&self.info)?;
+        self.encryption= conn.encryption();
+        self.cancel= Some(conn.cancel_handle());
+        self.conn= Some(Arc::new(Mutex::new(conn)));
+        self.message.push(format!("connected to {}\n",self.info.address()));
+        Ok(())
+    }
+    pub fn status(&self)-> String{
+        if let Some(start)= self.running{
+            let elapsed= start.elapsed();
+            let spinner= ['|','/','-','\\'][(elapsed.as_millis()/ 200% 4) as usize];
+            return format!("{} running {:.1}s, <C-c> to cancel",
+                           spinner,elapsed.as_secs_f32());
+        }
+        match self.conn{
+            Some(_)=> match (&self.encryption,self.info.engine()){
+                (_,Engine::Sqlite)=> format!("connected: {}",self.info.address()),
+                (Some(cipher),_)=> format!("connected: {} [tls: {}]",self.info.address(),cipher),
+                (None,_)=> format!("connected: {} [unencrypted]",self.info.address())
+            },
+            None=> String::from("disconnected")
+        }
+    }
+    fn check_connection(&mut self)-> bool{
+        let alive= self.conn
+            .as_ref()
+            .map(|conn| conn.try_lock().map_or(true,|mut conn| conn.ping()));
+        match alive{
+            Some(true)=> return true,
+            Some(false)=> {
+                self.message.push(String::from(
+                        "connection lost, reconnecting (session state was reset)\n"));
+            },
+            None=> {}
+        }
+        self.connect();
+        self.conn.is_some()
+    }
+    fn cancel_query(&self) {
+        if let Some(cancel)= &self.cancel{
+            let _= cancel.cancel();
+        }
+    }
+    fn idle(&mut self)-> Option<Connection>{
+        let conn= match &self.conn{
+            Some(conn)=> Arc::clone(conn),
+            None=> {
+                self.message.push(String::from("not connected\n"));
+                return None
+            }
+        };
+        if self.running.is_none()&& self.fetch.take().is_some(){
+            self.stopping= true;
+            self.message.push(String::from("stopping the paused fetch, try again in a moment\n"));
+            return None
+        }
+        Some(conn)
+    }
+    pub fn browse(&mut self,action: impl FnOnce(&mut Browser,&mut dyn Database)-> AppResult<()>) {
+        let conn= match self.idle(){
+            Some(conn)=> conn,
+            None=> return
+        };
+        let mut conn= match conn.try_lock(){
+            Ok(conn)=> conn,
+            Err(_)=> {
+                self.message.push(String::from("connection is busy, try again when the query is done\n"));
+                return
+            }
+        };
+        if let Err(err)= action(&mut self.browser,conn.as_mut()){
+            self.message.push(format!("{:?}\n",err));
+        }
+    }
+}
+
+#[derive(StructOpt,Clone)]
 pub struct DatabaseInfo{
     #[structopt(short,long,default_value= "root")]
     pub user: String,
@@ -97,33 +882,255 @@ pub struct DatabaseInfo{
     pub host: String,
     #[structopt(short,long,default_value= "")]
     pub database: String,
-    #[structopt(short="P",long,default_value= "3306")]
-    pub port: u16,
+    #[structopt(short="P",long)]
+    pub port: Option<u16>,
+    #[structopt(short="S",long)]
+    pub socket: Option<String>,
+    #[structopt(long)]
+    pub sqlite: Option<String>,
+    #[structopt(long)]
+    pub postgres: bool,
+    #[structopt(long)]
+    pub schema: Option<String>,
+    #[structopt(long)]
+    pub read_only: bool,
+    #[structopt(long,default_value= "100000")]
+    pub max_rows: usize,
+    #[structopt(long)]
+    pub profile: Option<String>,
+    #[structopt(long)]
+    pub ssl_mode: Option<String>,
+    #[structopt(long)]
+    pub ssl_ca: Option<String>,
+    #[structopt(long)]
+    pub ssl_cert: Option<String>,
+    #[structopt(long)]
+    pub ssl_key: Option<String>,
+    #[structopt(long)]
+    pub defaults_file: Option<String>,
+    #[structopt(long)]
+    pub password_command: Option<String>,
+    #[structopt(name= "URL")]
+    pub url: Option<String>,
+    #[structopt(skip)]
+    pub params: Vec<(String,String)>,
+    #[structopt(skip)]
+    pub explicit: Vec<String>,
+}
+
+#[derive(Clone,Copy,PartialEq)]
+pub enum Engine{
+    Mysql,
+    Postgres,
+    Sqlite
+}
+
+impl Engine{
+    pub fn quote_identifier(&self,name: &str)-> String{
+        match self{
+            Engine::Mysql=> format!("`{}`",name.replace('`',"``")),
+            Engine::Postgres| Engine::Sqlite=> format!("\"{}\"",name.replace('"',"\"\""))
+        }
+    }
 }
 
 impl DatabaseInfo{
-    /*
-    pub fn new()-> Self{
+    pub fn load()-> Self{
+        let matches= Self::clap().get_matches();
+        let mut info= Self::from_clap(&matches);
+        info.explicit= ["user","password","host","database","port","socket","sqlite","schema",
+                        "ssl-mode","ssl-ca","ssl-cert","ssl-key","max-rows"]
+            .iter()
+            .filter(|name| matches.occurrences_of(name)> 0)
+            .map(|name| name.to_string())
+            .collect();
+        info
+    }
+    pub fn is_explicit(&self,name: &str)-> bool{
+        self.explicit.iter().any(|arg| arg== name)
+    }
+    pub fn set(&mut self,name: &str,value: &str)-> AppResult<()>{
+        if self.is_explicit(name){
+            return Ok(());
+        }
+        match name{
+            "user"=> self.user= value.to_string(),
+            "password"=> self.password= value.to_string(),
+            "host"=> self.host= value.to_string(),
+            "database"=> self.database= value.to_string(),
+            "port"=> self.port= Some(value.parse()
+                                     .map_err(|_| format!("invalid port '{}'",value))?),
+            "socket"=> self.socket= Some(value.to_string()),
+            "sqlite"=> self.sqlite= Some(value.to_string()),
+            "schema"=> self.schema= Some(value.to_string()),
+            "ssl-mode"=> self.ssl_mode= Some(value.to_string()),
+            "ssl-ca"=> self.ssl_ca= Some(value.to_string()),
+            "ssl-cert"=> self.ssl_cert= Some(value.to_string()),
+            "ssl-key"=> self.ssl_key= Some(value.to_string()),
+            "max-rows"=> self.max_rows= value.parse()
+                .map_err(|_| format!("invalid max rows '{}'",value))?,
+            _=> return Ok(())
+        }
+        self.explicit.push(name.to_string());
+        Ok(())
+    }
+    pub fn engine(&self)-> Engine{
+        if self.sqlite.is_some(){
+            Engine::Sqlite
+        }else if self.postgres{
+            Engine::Postgres
+        }else {
+            Engine::Mysql
+        }
+    }
+    pub fn address(&self)-> String{
+        let address= match (self.engine(),self.socket_path()){
+            (Engine::Sqlite,_)=> self.sqlite.clone().unwrap_or_default(),
+            (_,Some(socket))=> format!("{}@{}/{} via {}",self.user,self.host,self.database,socket),
+            _=> format!("{}@{}:{}/{}",self.user,self.host,self.port(),self.database)
+        };
+        match self.read_only{
+            true=> format!("{} [read-only]",address),
+            false=> address
+        }
+    }
+    pub fn port(&self)-> u16{
+        match (self.port,self.engine()){
+            (Some(port),_)=> port,
+            (None,Engine::Postgres)=> 5432,
+            (None,_)=> 3306
+        }
+    }
+    pub fn socket_path(&self)-> Option<String>{
+        if self.engine()!= Engine::Mysql{
+            return None;
+        }
+        if self.socket.is_some(){
+            return self.socket.clone();
+        }
+        if self.host!= "localhost"|| self.port.is_some(){
+            return None;
+        }
+        ["/var/run/mysqld/mysqld.sock","/tmp/mysql.sock","/var/lib/mysql/mysql.sock"]
+            .iter()
+            .find(|path| std::path::Path::new(path).exists())
+            .map(|path| path.to_string())
+    }
+    pub fn need_password(&self)-> bool{
+        self.engine()!= Engine::Sqlite&& self.password.is_empty()
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum InfoEntries{
+    USER,
+    PASS,
+    HOST,
+    DATABASE,
+    PORT
+}
+
+impl InfoEntries {
+    pub fn next_entry(&self)-> Self{
+        match self{
+            Self::USER=> Self::PASS,
+            Self::PASS=> Self::HOST,
+            Self::HOST=> Self::DATABASE,
+            Self::DATABASE=> Self::PORT,
+            Self::PORT=> Self::PORT
+        }
+    }
+    pub fn prev_entry(&self)-> Self{
+        match self{
+            Self::USER=> Self::USER,
+            Self::PASS=> Self::USER,
+            Self::HOST=> Self::PASS,
+            Self::DATABASE=> Self::HOST,
+            Self::PORT=> Self::DATABASE
+        }
+    }
+}
+
+pub struct Login{
+    pub user: String,
+    pub password: String,
+    pub host: String,
+    pub database: String,
+    pub port: String,
+    pub current_entry: InfoEntries,
+    pub scroll: u16,
+    pub length: u16,
+    pub notice: Option<Result<String,String>>
+}
+
+impl Login{
+    pub fn from_info(info: &DatabaseInfo)-> Self{
         Self{
-            user: String::new(),
-            password: String::new(),
-            host: String::new(),
-            database: String::new(),
-            port: 0,
+            user: info.user.clone(),
+            password: info.password.clone(),
+            host: info.host.clone(),
+            database: info.database.clone(),
+            port: info.port.map(|port| port.to_string()).unwrap_or_default(),
+            current_entry: InfoEntries::PASS,
+            scroll: 0,
+            length: 0,
+            notice: None
         }
     }
-    */
-    pub fn build_opts(&mut self)-> OptsBuilder{
-        let user= self.user.clone();
-        let password= self.password.clone();
-        let host= self.host.clone();
-        let database= self.database.clone();
-        OptsBuilder::new()
-            .user(Some(user))
-            .pass(Some(password))
-            .ip_or_hostname(Some(host))
-            .db_name(Some(database))
-            .tcp_port(self.port)
+    pub fn apply(&self,info: &mut DatabaseInfo)-> Result<(),String>{
+        let port= match self.port.trim(){
+            ""=> None,
+            port=> match port.parse::<u16>(){
+                Ok(port) if port> 0=> Some(port),
+                _=> return Err(format!("invalid port '{}', expected 1-65535",port))
+            }
+        };
+        info.user= self.user.clone();
+        info.password= self.password.clone();
+        info.host= self.host.clone();
+        info.database= self.database.clone();
+        info.port= port;
+        Ok(())
+    }
+    pub fn get_length(&mut self,length: u16) {
+        self.length= length;
+    }
+    pub fn next_entry(&mut self) {
+        self.current_entry= self.current_entry.next_entry();
+    }
+    pub fn prev_entry(&mut self) {
+        self.current_entry= self.current_entry.prev_entry();
+    }
+    pub fn enter(&mut self,ch: char) {
+        match self.current_entry {
+            InfoEntries::USER=> self.user.push(ch),
+            InfoEntries::PASS=> self.password.push(ch),
+            InfoEntries::HOST=> self.host.push(ch),
+            InfoEntries::DATABASE=> self.database.push(ch),
+            InfoEntries::PORT=> self.port.push(ch),
+        };
+    }
+    pub fn delete(&mut self) {
+        match self.current_entry {
+            InfoEntries::USER=> self.user.pop(),
+            InfoEntries::PASS=> self.password.pop(),
+            InfoEntries::HOST=> self.host.pop(),
+            InfoEntries::DATABASE=> self.database.pop(),
+            InfoEntries::PORT=> self.port.pop(),
+        };
+    }
+    pub fn scroll_check(&mut self){
+        let length= self.length;
+        let mut scroll= |len: u16| {
+            self.scroll= len.saturating_sub(length.saturating_sub(5));
+        };
+        match self.current_entry {
+            InfoEntries::USER=> scroll(self.user.len() as u16),
+            InfoEntries::PASS=> scroll(self.password.len() as u16),
+            InfoEntries::HOST=> scroll(self.host.len() as u16),
+            InfoEntries::DATABASE=> scroll(self.database.len() as u16),
+            InfoEntries::PORT=> scroll(self.port.len() as u16),
+        }
     }
 }
 
@@ -134,11 +1141,478 @@ pub struct Message{
     pub scroll: (u16,u16)
 }
 
-impl Message{
-    pub fn new()-> Self{
+impl Default for Message {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Message{
+    pub fn new()-> Self{
+        Self{
+            context: Vec::new(),
+            current_line: 0,
+            size: (0,0),
+            scroll: (0,0)
+        }
+    }
+    pub fn get_size(&mut self,size: (u16,u16)){
+        self.size= size;
+    }
+    pub fn push(&mut self, context: String){
+        for line in context.split_inclusive('\n'){
+            self.context.push(line.to_string());
+            self.scroll_down();
+        }
+    }
+    pub fn scroll_up(&mut self) {
+        self.scroll.0= self.scroll.0.saturating_sub(1);
+    }
+    pub fn scroll_down(&mut self) {
+        if self.size.1> 0&& self.scroll.0+ self.size.1< self.context.len() as u16{
+            self.scroll.0= self.scroll.0.saturating_add(1);
+        }
+    }
+    pub fn scroll_left(&mut self) {
+        self.scroll.1= self.scroll.1.saturating_sub(1);
+    }
+    pub fn scroll_right(&mut self) {
+        self.scroll.1= self.scroll.1.saturating_add(1);
+    }
+}
+
+pub struct Editor{
+    pub cursor_index: usize,
+    pub line_index: usize,
+    pub size: (u16,u16),
+    pub scroll: (u16,u16),
+    pub context: Vec<String>,
+    pub selection: Option<(usize,usize)>
+}
+
+impl Default for Editor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Editor{
+    pub fn new()-> Self{
+        Self{
+            cursor_index: 0,
+            line_index: 0,
+            size: (0,0),
+            scroll: (0,0),
+            context: vec![String::from("\n")],
+            selection: None
+        }
+    }
+    pub fn getsize(&mut self,size: (u16,u16)){
+        self.size= size;
+    }
+    pub fn offset(&self,line: usize,index: usize)-> usize{
+        let text= &self.context[line];
+        let mut index= index.min(text.len());
+        while !text.is_char_boundary(index){
+            index-= 1;
+        }
+        self.context[..line]
+            .iter()
+            .map(|line| line.len())
+            .sum::<usize>()+ index
+    }
+    pub fn toggle_selection(&mut self){
+        self.selection= match self.selection{
+            Some(_)=> None,
+            None=> Some((self.line_index,self.cursor_index))
+        };
+    }
+    pub fn selection_range(&self)-> Option<(usize,usize)>{
+        let (line,index)= self.selection?;
+        let anchor= self.offset(line,index);
+        let cursor= self.offset(self.line_index,self.cursor_index);
+        Some((anchor.min(cursor),anchor.max(cursor)))
+    }
+    pub fn selected_text(&self)-> Option<String>{
+        let (start,end)= self.selection_range()?;
+        Some(self.context.concat()[start..end].to_string())
+    }
+    pub fn enter(&mut self, ch: char){
+        self.selection= None;
+        let line= self.line_index;
+        let index= self.cursor_index;
+
+        self.context[line].insert(index, ch);
+    }
+    pub fn delete(&mut self)-> bool{
+        self.selection= None;
+        let line= self.line_index;
+        let height= self.context.len();
+        let len= self.context[line].len();
+        let mut remove_or_pop= || {
+            if line< height- 1{
+                self.context.remove(line);
+            }else {
+                self.context.pop();
+            }
+        };
+        let mut delete_line= ||-> bool{
+            if height> 1&& len<= 1{
+                remove_or_pop();
+                true
+            } else{
+                false
+            }
+        };
+
+        if self.cursor_index> 0{
+            let current_index= self.cursor_index;
+            let new_index= self.context[line][..current_index]
+                .char_indices()
+                .next_back()
+                .map_or(0,|(index,_)| index);
+
+            self.context[line].replace_range(new_index..current_index,"");
+            self.cursor_index= new_index;
+            false
+        }else{
+            delete_line()
+        }
+    }
+    pub fn addline(&mut self){
+        self.selection= None;
+        let index= self.cursor_index;
+
+        if index== 0{
+            self.context.insert(self.line_index, String::from("\n"));
+            self.line_index+= 1;
+        } else {
+            let new:String = self.context[self.line_index]
+                .split_off(self.cursor_index);
+            self.context[self.line_index].push('\n');
+            self.line_index+= 1; 
+            self.context.insert(self.line_index, new);
+        }
+    }
+    pub fn append_line(&mut self,text: &str){
+        self.selection= None;
+        if self.context.last().is_some_and(|line| line.trim().is_empty()){
+            self.context.pop();
+        }
+        self.context.push(format!("{}\n",text));
+        self.line_index= self.context.len()- 1;
+        self.cursor_index= 0;
+        self.scroll_check();
+    }
+    pub fn cursor_left(&mut self) {
+        if let Some((index,_))= self.context[self.line_index][..self.cursor_index].char_indices().next_back(){
+            self.cursor_index= index;
+        }
+    }
+    pub fn cursor_right(&mut self) {
+        let line= self.line_index;
+
+        if self.cursor_index< self.context[line].len()- 1&&
+        self.context[line].len()< 0xfffe{
+            self.cursor_index+= self.context[line][self.cursor_index..]
+                .chars()
+                .next()
+                .map_or(1,char::len_utf8);
+        }
+    }
+    pub fn cursor_up(&mut self) {
+        if self.line_index> 0{
+            self.line_index-= 1;
+        }
+    }
+    pub fn cursor_down(&mut self) {
+        if self.line_index< self.context.len()- 1{
+            self.line_index+= 1;
+        }
+    }
+
+    pub fn cursor_check(&mut self,last: bool) {
+        let index= self.cursor_index;
+        let max= self.context[self.line_index].len()- 1;
+
+        if index> max|| last{
+            self.cursor_index= max;
+        }
+        while !self.context[self.line_index].is_char_boundary(self.cursor_index){
+            self.cursor_index-= 1;
+        }
+    }
+    pub fn scroll_check(&mut self) {
+        let x= self.cursor_index as u16;
+        let y= self.line_index as u16;
+        self.scroll.0= y.saturating_sub(self.size.0- 5);
+        self.scroll.1= x.saturating_sub(self.size.1- 5);
+    }
+}
+
+#[derive(Clone,Copy,PartialEq)]
+pub enum NodeKind{
+    Schema,
+    Table,
+    View,
+    Columns,
+    Indexes,
+    Triggers,
+    Item
+}
+
+pub struct Node{
+    pub name: String,
+    pub kind: NodeKind,
+    pub expanded: bool,
+    pub children: Option<Vec<Node>>
+}
+
+impl Node{
+    pub fn new(name: &str,kind: NodeKind)-> Self{
+        Self{
+            name: name.to_string(),
+            kind,
+            expanded: false,
+            children: None
+        }
+    }
+    pub fn label(&self)-> String{
+        let marker= match (self.kind,self.expanded){
+            (NodeKind::Item,_)=> ' ',
+            (_,true)=> '-',
+            (_,false)=> '+'
+        };
+        match self.kind{
+            NodeKind::View=> format!("{} {} (view)",marker,self.name),
+            NodeKind::Columns| NodeKind::Indexes| NodeKind::Triggers=> {
+                let count= self.children.as_ref().map_or(0,|children| children.len());
+                format!("{} {} ({})",marker,self.name,count)
+            },
+            _=> format!("{} {}",marker,self.name)
+        }
+    }
+    fn load(
+        &self,
+        db: &mut dyn Database,
+        path: &[String])-> AppResult<Vec<Node>>{
+        let (schema,table)= (&path[0],path.get(1).cloned().unwrap_or_default());
+        let items= |names: Vec<String>| {
+            names.iter().map(|name| Node::new(name,NodeKind::Item)).collect()
+        };
+        Ok(match self.kind{
+            NodeKind::Schema=> db.tables(schema)?
+                .iter()
+                .map(|(name,view)| Node::new(name,match view{
+                    true=> NodeKind::View,
+                    false=> NodeKind::Table
+                }))
+                .collect(),
+            NodeKind::Table| NodeKind::View=> {
+                let mut groups= vec![
+                    Node::new("columns",NodeKind::Columns),
+                    Node::new("indexes",NodeKind::Indexes),
+                    Node::new("triggers",NodeKind::Triggers)
+                ];
+                for group in groups.iter_mut(){
+                    group.children= Some(group.load(db,path)?);
+                }
+                groups[0].expanded= true;
+                groups
+            },
+            NodeKind::Columns=> items(db.columns(schema,&table)?),
+            NodeKind::Indexes=> items(db.indexes(schema,&table)?),
+            NodeKind::Triggers=> items(db.triggers(schema,&table)?),
+            NodeKind::Item=> Vec::new()
+        })
+    }
+}
+
+pub struct Browser{
+    pub nodes: Option<Vec<Node>>,
+    pub index: usize
+}
+
+impl Default for Browser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Browser{
+    pub fn new()-> Self{
+        Self{
+            nodes: None,
+            index: 0
+        }
+    }
+    pub fn visible(&self)-> Vec<(Vec<usize>,&Node)>{
+        fn walk<'a>(nodes: &'a [Node],path: &[usize],list: &mut Vec<(Vec<usize>,&'a Node)>){
+            for (index,node) in nodes.iter().enumerate(){
+                let mut path= path.to_vec();
+                path.push(index);
+                list.push((path.clone(),node));
+                if let (true,Some(children))= (node.expanded,&node.children){
+                    walk(children,&path,list);
+                }
+            }
+        }
+        let mut list= Vec::new();
+        if let Some(nodes)= &self.nodes{
+            walk(nodes,&[],&mut list);
+        }
+        list
+    }
+    pub fn selected(&self)-> Option<Vec<usize>>{
+        self.visible()
+            .into_iter()
+            .nth(self.index)
+            .map(|(path,_)| path)
+    }
+    fn names(&self,path: &[usize])-> Vec<String>{
+        let mut names= Vec::new();
+        let mut nodes= self.nodes.as_deref().unwrap_or_default();
+        for index in path{
+            names.push(nodes[*index].name.clone());
+            nodes= nodes[*index].children.as_deref().unwrap_or_default();
+        }
+        names
+    }
+    fn node(&mut self,path: &[usize])-> &mut Node{
+        let mut node= &mut self.nodes.as_mut().expect("browser not loaded")[path[0]];
+        for index in &path[1..]{
+            node= &mut node.children.as_mut().expect("node not loaded")[*index];
+        }
+        node
+    }
+    pub fn next(&mut self){
+        if self.index+ 1< self.visible().len(){
+            self.index+= 1;
+        }
+    }
+    pub fn prev(&mut self){
+        self.index= self.index.saturating_sub(1);
+    }
+    pub fn expand(&mut self,db: &mut dyn Database,reload: bool)-> AppResult<()>{
+        let path= match self.selected(){
+            Some(path)=> path,
+            None=> return Ok(())
+        };
+        let names= self.names(&path);
+        let node= self.node(&path);
+        if node.kind== NodeKind::Item{
+            return Ok(());
+        }
+        if node.children.is_none()|| reload{
+            node.children= Some(node.load(db,&names)?);
+        }
+        node.expanded= true;
+        Ok(())
+    }
+    pub fn collapse(&mut self){
+        let path= match self.selected(){
+            Some(path)=> path,
+            None=> return
+        };
+        let node= self.node(&path);
+        if node.expanded{
+            node.expanded= false;
+        }else if path.len()> 1{
+            let parent= &path[..path.len()- 1];
+            self.index= self.visible()
+                .iter()
+                .position(|(path,_)| path== parent)
+                .unwrap_or(0);
+        }
+    }
+    pub fn reload(&mut self,db: &mut dyn Database)-> AppResult<()>{
+        let nodes= db.schemas()?
+            .iter()
+            .map(|name| Node::new(name,NodeKind::Schema))
+            .collect();
+        self.nodes= Some(nodes);
+        self.index= 0;
+        Ok(())
+    }
+    pub fn selected_table(&self)-> Option<(String,String)>{
+        let path= self.selected()?;
+        let kind= self.visible()[self.index].1.kind;
+        if path.len()!= 2|| !matches!(kind,NodeKind::Table| NodeKind::View){
+            return None;
+        }
+        let names= self.names(&path);
+        Some((names[0].clone(),names[1].clone()))
+    }
+    pub fn select_statement(&self,db: &dyn Database)-> Option<String>{
+        let (schema,table)= self.selected_table()?;
+        Some(format!("SELECT * FROM {}.{} LIMIT 100;",
+                     db.quote_identifier(&schema),db.quote_identifier(&table)))
+    }
+}
+
+pub struct Panel{
+    pub title: String,
+    pub lines: Vec<String>,
+    pub wrap: bool,
+    pub size: (u16,u16),
+    pub scroll: (u16,u16)
+}
+
+impl Panel{
+    pub fn table(info: &TableInfo)-> Self{
+        let mut rows= vec![[
+            String::from("name"),String::from("type"),String::from("null"),
+            String::from("default"),String::from("key"),String::from("comment")
+        ]];
+        for column in &info.columns{
+            rows.push([
+                column.name.clone(),
+                column.kind.clone(),
+                String::from(match column.nullable{
+                    true=> "YES",
+                    false=> "NO"
+                }),
+                column.default.clone().unwrap_or(String::from("NULL")),
+                column.key.clone(),
+                column.comment.clone()
+            ]);
+        }
+        let widths: Vec<usize>= (0..6)
+            .map(|index| rows.iter().map(|row| row[index].chars().count()).max().unwrap_or(0))
+            .collect();
+
+        let mut lines= vec![String::from("columns")];
+        for row in rows{
+            let cells: Vec<String>= row.iter()
+                .zip(&widths)
+                .map(|(cell,width)| format!("{:<width$}",cell,width= width))
+                .collect();
+            lines.push(format!("  {}",cells.join("  ").trim_end()));
+        }
+        for (title,items) in [("indexes",&info.indexes),("foreign keys",&info.foreign_keys)]{
+            lines.push(String::new());
+            lines.push(title.to_string());
+            if items.is_empty(){
+                lines.push(String::from("  (none)"));
+            }
+            lines.extend(items.iter().map(|item| format!("  {}",item)));
+        }
+        lines.push(String::new());
+        lines.push(String::from("ddl"));
+        lines.extend(info.ddl.lines().map(|line| format!("  {}",line)));
         Self{
-            context: Vec::new(),
-            current_line: 0,
+            title: format!("{}.{}",info.schema,info.name),
+            lines,
+            wrap: false,
+            size: (0,0),
+            scroll: (0,0)
+        }
+    }
+    pub fn cell(title: String,value: &Value)-> Self{
+        Self{
+            title,
+            lines: cell_text(value).lines().map(|line| line.to_string()).collect(),
+            wrap: true,
             size: (0,0),
             scroll: (0,0)
         }
@@ -146,17 +1620,19 @@ impl Message{
     pub fn get_size(&mut self,size: (u16,u16)){
         self.size= size;
     }
-    pub fn push(&mut self, context: String){
-        self.context.push(context);
-        self.scroll_down();
-    }
-    pub fn scroll_up(&mut self) {
-        self.scroll.0= self.scroll.0.saturating_sub(1);
+    pub fn scroll_up(&mut self,lines: u16) {
+        self.scroll.0= self.scroll.0.saturating_sub(lines);
     }
-    pub fn scroll_down(&mut self) {
-        if self.scroll.0+ self.size.1< self.context.len() as u16{
-            self.scroll.0= self.scroll.0.saturating_add(1);
-        }
+    pub fn scroll_down(&mut self,lines: u16) {
+        let height= match self.wrap{
+            true=> self.lines
+                .iter()
+                .map(|line| line.chars().count().div_ceil(self.size.0.max(1) as usize).max(1))
+                .sum::<usize>() as u16,
+            false=> self.lines.len() as u16
+        };
+        let max= height.saturating_sub(self.size.1);
+        self.scroll.0= self.scroll.0.saturating_add(lines).min(max);
     }
     pub fn scroll_left(&mut self) {
         self.scroll.1= self.scroll.1.saturating_sub(1);
@@ -166,139 +1642,134 @@ impl Message{
     }
 }
 
-pub struct Editor{
-    pub cursor_index: usize,
-    pub line_index: usize,
-    pub size: (u16,u16),
-    pub scroll: (u16,u16),
-    pub context: Vec<String>
-}
-
-impl Editor{
-    pub fn new()-> Self{
-        Self{
-            cursor_index: 0,
-            line_index: 0,
-            size: (0,0),
-            scroll: (0,0),
-            context: vec![String::from("\n")]
-        }
-    }
-    pub fn getsize(&mut self,size: (u16,u16)){
-        self.size= size;
+pub fn cell_text(value: &Value)-> String{
+    match value{
+        Value::Bytes(bytes)=> hex_dump(bytes),
+        Value::Text(text)=> {
+            let trimmed= text.trim_start();
+            if trimmed.starts_with('{')|| trimmed.starts_with('['){
+                if let Ok(json)= serde_json::from_str::<serde_json::Value>(text){
+                    return serde_json::to_string_pretty(&json).unwrap_or(text.clone());
+                }
+            }
+            text.clone()
+        },
+        value=> value.display()
     }
-    pub fn enter(&mut self, ch: char){
-        let line= self.line_index;
-        let index= self.cursor_index;
+}
 
-        self.context[line].insert(index, ch);
+fn hex_dump(bytes: &[u8])-> String{
+    let mut dump= format!("{} bytes\n",bytes.len());
+    for (index,chunk) in bytes.chunks(16).enumerate(){
+        let hex: Vec<String>= chunk.iter().map(|byte| format!("{:02x}",byte)).collect();
+        let ascii: String= chunk.iter()
+            .map(|byte| match byte.is_ascii_graphic()|| *byte== b' '{
+                true=> *byte as char,
+                false=> '.'
+            })
+            .collect();
+        dump.push_str(&format!("{:08x}  {:<47}  |{}|\n",index* 16,hex.join(" "),ascii));
     }
-    pub fn delete(&mut self)-> bool{
-        let line= self.line_index;
-        let height= self.context.len();
-        let len= self.context[line].len();
-        let mut remove_or_pop= || {
-            if line< height- 1{
-                self.context.remove(line);
-            }else {
-                self.context.pop();
-            }
-        };
-        let mut delete_line= ||-> bool{
-            if height> 1&& len<= 1{
-                remove_or_pop();
-                return true;
-            } else{
-                return false;
-            }
-        };
-
-        if self.cursor_index> 0{
-            let current_index= self.cursor_index;
-            let new_index= current_index- 1;
+    dump
+}
 
-            let before_chars= self.context[line]
-                .chars()
-                .take(new_index);
-            let after_chars= self.context[line]
-                .chars()
-                .skip(current_index);
+pub struct Results{
+    pub tables: Vec<Table>,
+    pub index: usize,
+    fresh: bool
+}
 
-            self.context[line]= before_chars
-                .chain(after_chars).collect();
-            return false;
-        }else{
-            return delete_line();
-        }
+impl Default for Results {
+    fn default() -> Self {
+        Self::new()
     }
-    pub fn addline(&mut self){
-        let index= self.cursor_index;
+}
 
-        if index== 0{
-            self.context.insert(self.line_index, String::from("\n"));
-            self.line_index+= 1;
-        } else {
-            let new:String = self.context[self.line_index]
-                .split_off(self.cursor_index);
-            self.context[self.line_index].push('\n');
-            self.line_index+= 1; 
-            self.context.insert(self.line_index, new);
+impl Results {
+    pub fn new()-> Self{
+        Self {
+            tables: vec![Table::new()],
+            index: 0,
+            fresh: false
         }
     }
-    pub fn cursor_left(&mut self) {
-        if self.cursor_index> 0{
-            self.cursor_index-= 1;
-        }
+    pub fn start_batch(&mut self){
+        self.fresh= true;
     }
-    pub fn cursor_right(&mut self) {
-        let line= self.line_index;
-
-        if self.cursor_index< self.context[line].len()- 1&&
-        self.context[line].len()< 0xfffe{
-            self.cursor_index+= 1;
+    pub fn push(&mut self,result: QueryResult){
+        if result.headers.is_empty(){
+            return;
         }
-    }
-    pub fn cursor_up(&mut self) {
-        if self.line_index> 0{
-            self.line_index-= 1;
+        if self.fresh{
+            self.tables.clear();
+            self.index= 0;
+            self.fresh= false;
         }
+        let mut table= Table::new();
+        table.get_table(result);
+        self.tables.push(table);
     }
-    pub fn cursor_down(&mut self) {
-        if self.line_index< self.context.len()- 1{
-            self.line_index+= 1;
+    pub fn append(&mut self,rows: Vec<Vec<Value>>,fetch: Fetch){
+        if let Some(table)= self.tables.last_mut(){
+            table.append(rows,fetch);
         }
     }
-
-    pub fn cursor_check(&mut self,last: bool) {
-        let index= self.cursor_index;
-        let max= self.context[self.line_index].len()- 1;
-
-        if index> max|| last{
-            self.cursor_index= max;
+    pub fn current(&mut self)-> &mut Table{
+        &mut self.tables[self.index]
+    }
+    pub fn next_tab(&mut self){
+        if self.index+ 1< self.tables.len(){
+            self.index+= 1;
         }
     }
-    pub fn scroll_check(&mut self) {
-        let x= self.cursor_index as u16;
-        let y= self.line_index as u16;
-        self.scroll.0= y.saturating_sub(self.size.0- 5);
-        self.scroll.1= x.saturating_sub(self.size.1- 5);
+    pub fn prev_tab(&mut self){
+        self.index= self.index.saturating_sub(1);
     }
 }
 
 pub struct Table{
-    pub rows: Vec<Row>,
+    pub query: String,
     pub headers: Vec<String>,
-    pub items: Vec<Vec<String>>,
+    pub types: Vec<String>,
+    pub items: Vec<Vec<Value>>,
+    pub fetch: Fetch,
+    pub requested: bool,
+    pub view: Vec<usize>,
+    pub sort: Option<(usize,bool)>,
+    pub filter: Option<String>,
+    pub edits: Option<Edits>,
+    pub pending: usize,
+    pub widths: Vec<u16>,
+    pub cursor: (usize,usize),
     pub scroll: (u16,u16),
     pub size: (u16,u16)
 }
 
+impl Default for Table {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+const SAMPLE_ROWS: usize= 200;
+const MAX_WIDTH: u16= 40;
+
 impl Table {
     pub fn new()-> Self{
         Self {
-            rows: Vec::new(),
+            query: String::new(),
             headers: Vec::new(),
+            types: Vec::new(),
             items: Vec::new(),
+            fetch: Fetch::Done,
+            requested: false,
+            view: Vec::new(),
+            sort: None,
+            filter: None,
+            edits: None,
+            pending: 0,
+            widths: Vec::new(),
+            cursor: (0,0),
             scroll: (0,0),
             size: (0,0)
         }
@@ -306,40 +1777,312 @@ impl Table {
     pub fn get_size(&mut self,size: (u16,u16)){
         self.size= size;
     }
-    pub fn get_table(&mut self,rows: Vec<Row>){
-        if !rows.is_empty() {
-            self.rows= rows.clone();
-            self.headers.clear();
-            self.items.clear();
-            for column in rows[0].columns_ref(){
-                self.headers.push(column.name_str().to_string());
-            }
-            for row in rows{
-                let mut item: Vec<String>= Vec::new();
-                for value in row.unwrap(){
-                    item.push(value.as_sql(false));
+    pub fn get_table(&mut self,result: QueryResult){
+        if !result.headers.is_empty() {
+            self.query= result.query;
+            self.headers= result.headers;
+            self.types= result.types;
+            self.items= result.rows;
+            self.fetch= result.fetch;
+            self.requested= false;
+            self.sort= None;
+            self.filter= None;
+            self.edits= None;
+            self.pending= 0;
+            self.refresh_view();
+            self.fit_widths();
+        }
+    }
+    pub fn fit_widths(&mut self){
+        self.widths= self.headers
+            .iter()
+            .enumerate()
+            .map(|(index,header)| {
+                self.items
+                    .iter()
+                    .take(SAMPLE_ROWS)
+                    .filter_map(|row| row.get(index))
+                    .map(|cell| cell.display().chars().count())
+                    .chain([header.chars().count()])
+                    .max()
+                    .unwrap_or(0)
+                    .clamp(1,MAX_WIDTH as usize) as u16
+            })
+            .collect();
+    }
+    pub fn visible_widths(&self)-> Vec<u16>{
+        let mut widths= Vec::new();
+        let mut used= 0;
+        for width in self.widths.iter().skip(self.scroll.0 as usize){
+            if used+ width> self.size.0{
+                if widths.is_empty(){
+                    widths.push(self.size.0);
                 }
-                self.items.push(item);
+                break;
+            }
+            used+= width+ 1;
+            widths.push(*width);
+        }
+        widths
+    }
+    pub fn widen_col(&mut self){
+        if let Some(width)= self.widths.get_mut(self.cursor.1){
+            *width= width.saturating_add(2).min(self.size.0.max(1));
+        }
+    }
+    pub fn shrink_col(&mut self){
+        if let Some(width)= self.widths.get_mut(self.cursor.1){
+            *width= width.saturating_sub(2).max(1);
+        }
+    }
+    pub fn selected(&self)-> Option<&Value>{
+        self.items.get(*self.view.get(self.cursor.0)?)?.get(self.cursor.1)
+    }
+    fn selected_row(&self)-> Option<usize>{
+        self.view.get(self.cursor.0).copied()
+    }
+    pub fn template(&self)-> &Value{
+        let col= self.cursor.1;
+        match self.selected(){
+            Some(value) if *value!= Value::Null=> value,
+            _=> self.items
+                .iter()
+                .filter_map(|row| row.get(col))
+                .find(|value| **value!= Value::Null)
+                .unwrap_or(&Value::Null)
+        }
+    }
+    pub fn set_cell(&mut self,value: Value){
+        let (row,col)= match self.selected_row(){
+            Some(row) if self.cursor.1< self.headers.len()=> (row,self.cursor.1),
+            _=> return
+        };
+        if let Some(edits)= &mut self.edits{
+            edits.set(&mut self.items,row,col,value);
+        }
+        self.count_pending();
+    }
+    pub fn toggle_delete(&mut self){
+        if let (Some(row),Some(edits))= (self.selected_row(),&mut self.edits){
+            if !edits.deleted.remove(&row){
+                edits.deleted.insert(row);
+            }
+        }
+        self.count_pending();
+    }
+    pub fn add_row(&mut self){
+        let edits= match &mut self.edits{
+            Some(edits)=> edits,
+            None=> return
+        };
+        edits.inserted.insert(self.items.len());
+        self.view.push(self.items.len());
+        self.items.push(vec![Value::Null;self.headers.len()]);
+        self.move_rows(self.view.len() as isize);
+    }
+    pub fn revert_row(&mut self){
+        if let (Some(row),Some(edits))= (self.selected_row(),&mut self.edits){
+            edits.revert(&mut self.items,row);
+        }
+        self.count_pending();
+    }
+    fn count_pending(&mut self){
+        self.pending= self.edits.as_ref().map_or(0,|edits| edits.statements(&self.items).len());
+    }
+    fn finish_edits(&mut self){
+        let edits= match &mut self.edits{
+            Some(edits)=> edits,
+            None=> return
+        };
+        let deleted= std::mem::take(&mut edits.deleted);
+        edits.original.clear();
+        edits.inserted.clear();
+        self.items= std::mem::take(&mut self.items)
+            .into_iter()
+            .enumerate()
+            .filter(|(index,_)| !deleted.contains(index))
+            .map(|(_,row)| row)
+            .collect();
+        self.pending= 0;
+        self.refresh_view();
+    }
+    pub fn rows(&self)-> impl Iterator<Item= &Vec<Value>>{
+        self.view.iter().map(|index| &self.items[*index])
+    }
+    pub fn sort_col(&mut self){
+        let col= self.cursor.1;
+        if col>= self.headers.len(){
+            return;
+        }
+        self.sort= match self.sort{
+            Some((sorted,true)) if sorted== col=> Some((col,false)),
+            Some((sorted,false)) if sorted== col=> None,
+            _=> Some((col,true))
+        };
+        self.refresh_view();
+    }
+    pub fn set_filter(&mut self,filter: &str){
+        self.filter= match filter.trim(){
+            ""=> None,
+            filter=> Some(filter.to_string())
+        };
+        self.refresh_view();
+    }
+    pub fn append(&mut self,rows: Vec<Vec<Value>>,fetch: Fetch){
+        let selected= self.selected_row();
+        let start= self.items.len();
+        self.items.extend(rows);
+        let filter= self.filter.as_deref().map(|text| Filter::parse(text,&self.headers));
+        self.view.extend((start..self.items.len())
+            .filter(|index| filter.as_ref().is_none_or(|filter| filter.matches(&self.items[*index]))));
+        if self.sort.is_some(){
+            self.sort_view();
+            if let Some(row)= selected{
+                self.cursor.0= self.view.iter().position(|index| *index== row).unwrap_or(0);
+                self.move_rows(0);
             }
-            self.scroll= (0,0);
+        }
+        self.fetch= fetch;
+        self.requested= false;
+    }
+    fn refresh_view(&mut self){
+        let filter= self.filter.as_deref().map(|text| Filter::parse(text,&self.headers));
+        self.view= (0..self.items.len())
+            .filter(|index| filter.as_ref().is_none_or(|filter| filter.matches(&self.items[*index])))
+            .collect();
+        self.sort_view();
+        self.cursor.0= 0;
+        self.scroll.1= 0;
+    }
+    fn sort_view(&mut self){
+        if let Some((col,ascending))= self.sort{
+            self.view.sort_by(|a,b| {
+                let order= compare(&self.items[*a][col],&self.items[*b][col]);
+                match ascending{
+                    true=> order,
+                    false=> order.reverse()
+                }
+            });
         }
     }
     pub fn next_col(&mut self){
-        if self.scroll.0 as usize+ 3< self.headers.len(){
-            self.scroll.0= self.scroll.0.saturating_add(1);
+        if self.cursor.1+ 1< self.headers.len(){
+            self.cursor.1+= 1;
+        }
+        while self.cursor.1>= self.scroll.0 as usize+ self.visible_widths().len().max(1){
+            self.scroll.0+= 1;
         }
     }
     pub fn prev_col(&mut self){
-        self.scroll.0= self.scroll.0.saturating_sub(1);
+        self.cursor.1= self.cursor.1.saturating_sub(1);
+        if self.cursor.1< self.scroll.0 as usize{
+            self.scroll.0= self.cursor.1 as u16;
+        }
     }
     
     pub fn next_row(&mut self){
-        if (self.scroll.1 as usize+ self.size.1 as usize)< self.items.len()+ 3{
-            self.scroll.1= self.scroll.1.saturating_add(1);
-        }
+        self.move_rows(1);
     }
     pub fn prev_row(&mut self){
-        self.scroll.1= self.scroll.1.saturating_sub(1);
+        self.move_rows(-1);
+    }
+    pub fn next_page(&mut self){
+        self.move_rows(self.size.1.saturating_sub(1).max(1) as isize);
+    }
+    pub fn prev_page(&mut self){
+        self.move_rows(-(self.size.1.saturating_sub(1).max(1) as isize));
+    }
+    fn move_rows(&mut self,rows: isize){
+        let last= self.view.len().saturating_sub(1);
+        self.cursor.0= self.cursor.0.saturating_add_signed(rows).min(last);
+        let height= self.size.1.saturating_sub(1).max(1) as usize;
+        if self.cursor.0< self.scroll.1 as usize{
+            self.scroll.1= self.cursor.0 as u16;
+        }else if self.cursor.0>= self.scroll.1 as usize+ height{
+            self.scroll.1= (self.cursor.0+ 1- height) as u16;
+        }
+    }
+}
+fn compare(a: &Value,b: &Value)-> std::cmp::Ordering{
+    let number= |value: &Value| match value{
+        Value::Int(value)=> Some(*value as f64),
+        Value::UInt(value)=> Some(*value as f64),
+        Value::Float(value)=> Some(*value),
+        Value::Decimal(value)=> value.parse().ok(),
+        _=> None
+    };
+    match (a,b){
+        (Value::Null,Value::Null)=> std::cmp::Ordering::Equal,
+        (Value::Null,_)=> std::cmp::Ordering::Less,
+        (_,Value::Null)=> std::cmp::Ordering::Greater,
+        (Value::Int(a),Value::Int(b))=> a.cmp(b),
+        (Value::UInt(a),Value::UInt(b))=> a.cmp(b),
+        _=> match (number(a),number(b)){
+            (Some(a),Some(b))=> a.total_cmp(&b),
+            _=> a.display().cmp(&b.display())
+        }
+    }
+}
+
+pub enum Filter{
+    Text(String),
+    Compare(usize,String,String)
+}
+
+impl Filter{
+    pub fn parse(text: &str,headers: &[String])-> Self{
+        let text= text.trim();
+        for op in ["<=",">=","!=","<>","=","<",">","~"]{
+            if let Some((col,value))= text.split_once(op){
+                let col= col.trim();
+                if let Some(index)= headers.iter().position(|header| header.eq_ignore_ascii_case(col)){
+                    let value= value.trim();
+                    let value= value.strip_prefix('\'')
+                        .and_then(|value| value.strip_suffix('\''))
+                        .unwrap_or(value);
+                    return Self::Compare(index,op.to_string(),value.to_string());
+                }
+            }
+        }
+        Self::Text(text.to_lowercase())
+    }
+    pub fn matches(&self,row: &[Value])-> bool{
+        match self{
+            Self::Text(text)=> row.iter().any(|value| value.display().to_lowercase().contains(text)),
+            Self::Compare(col,op,text)=> {
+                let value= match row.get(*col){
+                    Some(value)=> value,
+                    None=> return false
+                };
+                if text.eq_ignore_ascii_case("null"){
+                    let null= matches!(value,Value::Null);
+                    return match op.as_str(){
+                        "="=> null,
+                        "!="| "<>"=> !null,
+                        _=> false
+                    };
+                }
+                if matches!(value,Value::Null){
+                    return false;
+                }
+                if op== "~"{
+                    return value.display().to_lowercase().contains(&text.to_lowercase());
+                }
+                let order= match (value.display().parse::<f64>(),text.parse::<f64>()){
+                    (Ok(a),Ok(b)) if value.is_numeric()=> a.total_cmp(&b),
+                    _=> value.display().as_str().cmp(text.as_str())
+                };
+                match op.as_str(){
+                    "="=> order.is_eq(),
+                    "!="| "<>"=> order.is_ne(),
+                    "<"=> order.is_lt(),
+                    ">"=> order.is_gt(),
+                    "<="=> order.is_le(),
+                    ">="=> order.is_ge(),
+                    _=> false
+                }
+            }
+        }
     }
 }
 //use structopt::StructOpt;
@@ -369,76 +2112,3 @@ pub scroll_check{
         }
 }
  */
-/*
-#[derive(Clone, Copy)]
-pub enum InfoEntries{
-    USER,
-    PASS,
-    HOST,
-    DATABASE,
-    PORT
-}
-
-impl InfoEntries {
-    pub fn next(&mut self)-> Self{
-        match self{
-            Self::USER=> Self::PASS,
-            Self::PASS=> Self::HOST,
-            Self::HOST=> Self::DATABASE,
-            Self::DATABASE=> Self::PORT,
-            Self::PORT=> Self::PORT
-        }
-    }
-    pub fn prev(&mut self)-> Self{
-        match self{
-            Self::USER=> Self::USER,
-            Self::PASS=> Self::USER,
-            Self::HOST=> Self::PASS,
-            Self::DATABASE=> Self::HOST,
-            Self::PORT=> Self::DATABASE
-        }
-    }
-}
-*/
-/*
-    pub fn get_length(&mut self,length: u16) {
-        self.length= length;
-    }
-    pub fn next_entry(&mut self) {
-        self.current_entry= self.current_entry.next();
-    }
-    pub fn prev_entry(&mut self) {
-        self.current_entry= self.current_entry.prev();
-    }
-    pub fn enter(&mut self,ch: char) {
-        match self.current_entry {
-            InfoEntries::USER=> self.user.push(ch),
-            InfoEntries::PASS=> self.password.push(ch),
-            InfoEntries::HOST=> self.host.push(ch),
-            InfoEntries::DATABASE=> self.database.push(ch),
-            InfoEntries::PORT=> self.port.push(ch),
-        };
-    }
-    pub fn delete(&mut self) {
-        match self.current_entry {
-            InfoEntries::USER=> self.user.pop(),
-            InfoEntries::PASS=> self.password.pop(),
-            InfoEntries::HOST=> self.host.pop(),
-            InfoEntries::DATABASE=> self.database.pop(),
-            InfoEntries::PORT=> self.port.pop(),
-        };
-    }
-    pub fn scroll_check(&mut self){
-
-        let mut scroll= |len: u16| {
-            self.scroll= len.saturating_sub(self.length- 5);
-        };
-        match self.current_entry {
-            InfoEntries::USER=> scroll(self.user.len() as u16),
-            InfoEntries::PASS=> scroll(self.password.len() as u16),
-            InfoEntries::HOST=> scroll(self.host.len() as u16),
-            InfoEntries::DATABASE=> scroll(self.database.len() as u16),
-            InfoEntries::PORT=> scroll(self.port.len() as u16),
-        }
-    }
-*/
@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use percent_encoding::percent_decode_str;
+use serde::Deserialize;
+use url::Url;
+
+use crate::app::{AppResult, DatabaseInfo, Engine};
+
+#[derive(Deserialize,Default)]
+pub struct Config{
+    #[serde(default)]
+    pub profiles: BTreeMap<String,Profile>
+}
+
+#[derive(Deserialize,Default,Clone)]
+#[serde(default)]
+pub struct Profile{
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub socket: Option<String>,
+    pub user: Option<String>,
+    pub database: Option<String>,
+    pub schema: Option<String>,
+    pub read_only: Option<bool>,
+    pub max_rows: Option<usize>,
+    pub postgres: Option<bool>,
+    pub sqlite: Option<String>
+}
+
+impl Config{
+    pub fn path()-> Option<PathBuf>{
+        dirs::config_dir().map(|dir| dir.join("sqltui").join("config.toml"))
+    }
+    pub fn load()-> AppResult<Self>{
+        let path= match Self::path(){
+            Some(path) if path.exists()=> path,
+            _=> return Ok(Self::default())
+        };
+        let text= fs::read_to_string(&path)?;
+        toml::from_str(&text)
+            .map_err(|err| format!("{}: {}",path.display(),err).into())
+    }
+}
+
+impl Profile{
+    pub fn apply(&self,info: &mut DatabaseInfo)-> AppResult<()>{
+        let fields= [
+            ("host",&self.host),
+            ("socket",&self.socket),
+            ("user",&self.user),
+            ("database",&self.database),
+            ("schema",&self.schema),
+            ("sqlite",&self.sqlite)
+        ];
+        for (name,value) in fields{
+            if let Some(value)= value{
+                info.set(name,value)?;
+            }
+        }
+        if let Some(port)= self.port{
+            info.set("port",&port.to_string())?;
+        }
+        if let Some(max_rows)= self.max_rows{
+            info.set("max-rows",&max_rows.to_string())?;
+        }
+        info.postgres|= self.postgres.unwrap_or(false);
+        info.read_only|= self.read_only.unwrap_or(false);
+        Ok(())
+    }
+    pub fn describe(&self)-> String{
+        if let Some(path)= &self.sqlite{
+            return format!("sqlite {}",path);
+        }
+        format!("{}@{}{}{}{}",
+                self.user.clone().unwrap_or_default(),
+                self.host.clone().unwrap_or_default(),
+                self.port.map(|port| format!(":{}",port)).unwrap_or_default(),
+                self.database.clone().map(|db| format!("/{}",db)).unwrap_or_default(),
+                match self.read_only{
+                    Some(true)=> " [read-only]",
+                    _=> ""
+                })
+    }
+}
+
+impl DatabaseInfo{
+    pub fn apply_url(&mut self)-> AppResult<()>{
+        let url= match &self.url{
+            Some(url)=> Url::parse(url)?,
+            None=> return Ok(())
+        };
+        let decode= |text: &str|-> AppResult<String> {
+            Ok(percent_decode_str(text).decode_utf8()?.to_string())
+        };
+        match url.scheme(){
+            "mysql"=> self.postgres= false,
+            "postgres"| "postgresql"=> self.postgres= true,
+            "sqlite"=> {
+                let host= url.host_str().unwrap_or_default();
+                return self.set("sqlite",&decode(&format!("{}{}",host,url.path()))?);
+            },
+            scheme=> return Err(format!("unsupported url scheme '{}'",scheme).into())
+        }
+        if !url.username().is_empty(){
+            self.set("user",&decode(url.username())?)?;
+        }
+        if let Some(password)= url.password(){
+            self.set("password",&decode(password)?)?;
+        }
+        if let Some(host)= url.host_str(){
+            self.set("host",host.trim_start_matches('[').trim_end_matches(']'))?;
+        }
+        if let Some(port)= url.port(){
+            self.set("port",&port.to_string())?;
+        }
+        let database= url.path().trim_start_matches('/');
+        if !database.is_empty(){
+            self.set("database",&decode(database)?)?;
+        }
+        self.params.clear();
+        for (key,value) in url.query_pairs(){
+            match key.replace('_',"-").as_str(){
+                name @ ("socket"| "ssl-mode"| "ssl-ca"| "ssl-cert"| "ssl-key")
+                    if self.engine()== Engine::Mysql=> self.set(name,&value)?,
+                _=> self.params.push((key.to_string(),value.to_string()))
+            }
+        }
+        Ok(())
+    }
+}
+
+impl DatabaseInfo{
+    pub fn apply_credentials(&mut self)-> AppResult<()>{
+        if let Some(command)= self.password_command.clone(){
+            if !self.is_explicit("password"){
+                let output= Command::new("sh").arg("-c").arg(&command).output()?;
+                if !output.status.success(){
+                    return Err(format!("password command '{}' failed ({}) {}",command,output.status,
+                                       String::from_utf8_lossy(&output.stderr).trim()).into());
+                }
+                let password= String::from_utf8(output.stdout)?;
+                self.set("password",password.lines().next().unwrap_or_default())?;
+            }
+        }
+        if self.engine()!= Engine::Mysql{
+            return Ok(());
+        }
+        if let Ok(password)= env::var("MYSQL_PWD"){
+            self.set("password",&password)?;
+        }
+        let path= match &self.defaults_file{
+            Some(path)=> PathBuf::from(path),
+            None=> match dirs::home_dir(){
+                Some(home) if home.join(".my.cnf").exists()=> home.join(".my.cnf"),
+                _=> return Ok(())
+            }
+        };
+        let text= fs::read_to_string(&path)
+            .map_err(|err| format!("{}: {}",path.display(),err))?;
+        for (key,value) in client_options(&text){
+            self.set(&key,&value)?;
+        }
+        Ok(())
+    }
+}
+
+fn client_options(text: &str)-> Vec<(String,String)>{
+    let mut options= Vec::new();
+    let mut client= false;
+    for line in text.lines(){
+        let line= line.trim();
+        if line.is_empty()|| line.starts_with('#')|| line.starts_with(';'){
+            continue;
+        }
+        if line.starts_with('['){
+            client= line.trim_matches(['[',']']).trim().eq_ignore_ascii_case("client");
+            continue;
+        }
+        if !client{
+            continue;
+        }
+        let (key,value)= match line.split_once('='){
+            Some((key,value))=> (key.trim(),value.trim()),
+            None=> continue
+        };
+        let value= match (value.chars().next(),value.chars().last()){
+            (Some('"'),Some('"'))| (Some('\''),Some('\'')) if value.len()>= 2=> &value[1..value.len()- 1],
+            _=> value
+        };
+        let key= match key.replace('_',"-").as_str(){
+            "pass"=> String::from("password"),
+            key=> key.to_string()
+        };
+        options.push((key,value.to_string()));
+    }
+    options
+}
@@ -0,0 +1,258 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::app::{AppResult, DatabaseInfo, Engine};
+
+pub mod mysql;
+pub mod postgres;
+pub mod sqlite;
+
+pub type Connection= Arc<Mutex<Box<dyn Database>>>;
+
+#[derive(Debug,Clone,PartialEq)]
+pub enum Value{
+    Null,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Decimal(String),
+    Text(String),
+    Bytes(Vec<u8>),
+    DateTime(String)
+}
+
+impl Value{
+    pub fn display(&self)-> String{
+        match self{
+            Value::Null=> String::from("NULL"),
+            Value::Bool(value)=> value.to_string(),
+            Value::Int(value)=> value.to_string(),
+            Value::UInt(value)=> value.to_string(),
+            Value::Float(value)=> value.to_string(),
+            Value::Decimal(value)| Value::Text(value)| Value::DateTime(value)=> value.clone(),
+            Value::Bytes(bytes)=> format!("0x{}",hex(bytes))
+        }
+    }
+    pub fn to_sql(&self,engine: Engine)-> String{
+        match self{
+            Value::Null=> String::from("NULL"),
+            Value::Bool(value)=> String::from(match value{
+                true=> "TRUE",
+                false=> "FALSE"
+            }),
+            Value::Text(value)| Value::DateTime(value)=> match engine{
+                Engine::Mysql=> format!("'{}'",value.replace('\\',"\\\\").replace('\'',"''")),
+                _=> format!("'{}'",value.replace('\'',"''"))
+            },
+            Value::Bytes(bytes)=> match engine{
+                Engine::Postgres=> format!("'\\x{}'::bytea",hex(bytes)),
+                _=> format!("X'{}'",hex(bytes))
+            },
+            _=> self.display()
+        }
+    }
+    pub fn parse_like(&self,text: &str)-> Value{
+        let fallback= || Value::Text(text.to_string());
+        let finite= text.parse::<f64>().is_ok_and(f64::is_finite);
+        match self{
+            Value::Bool(_)=> match text.to_lowercase().as_str(){
+                "true"| "t"| "1"=> Value::Bool(true),
+                "false"| "f"| "0"=> Value::Bool(false),
+                _=> fallback()
+            },
+            Value::Int(_)=> text.parse().map_or_else(|_| fallback(),Value::Int),
+            Value::UInt(_)=> text.parse().map_or_else(|_| fallback(),Value::UInt),
+            Value::Float(_) if finite=> text.parse().map_or_else(|_| fallback(),Value::Float),
+            Value::Decimal(_) if finite=> Value::Decimal(text.to_string()),
+            Value::Bytes(_)=> text.strip_prefix("0x")
+                .and_then(unhex)
+                .map_or_else(|| Value::Bytes(text.as_bytes().to_vec()),Value::Bytes),
+            Value::DateTime(_)=> Value::DateTime(text.to_string()),
+            _=> fallback()
+        }
+    }
+    pub fn is_numeric(&self)-> bool{
+        matches!(self,Value::Int(_)| Value::UInt(_)| Value::Float(_)| Value::Decimal(_))
+    }
+}
+
+pub fn hex(bytes: &[u8])-> String{
+    bytes.iter().map(|byte| format!("{:02X}",byte)).collect()
+}
+
+pub fn unhex(text: &str)-> Option<Vec<u8>>{
+    (0..text.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(text.get(index..index+ 2)?,16).ok())
+        .collect()
+}
+
+#[derive(Debug,Default,Clone,Copy,PartialEq)]
+pub enum Fetch{
+    #[default]
+    Done,
+    More,
+    Truncated,
+    Stopped
+}
+
+#[derive(Debug,Default)]
+pub struct QueryResult{
+    pub query: String,
+    pub headers: Vec<String>,
+    pub types: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+    pub fetch: Fetch,
+    pub affected_rows: u64,
+    pub matched_rows: Option<u64>,
+    pub last_insert_id: Option<u64>,
+    pub warnings: Vec<String>,
+    pub elapsed: Duration
+}
+
+impl QueryResult{
+    pub fn summary(&self)-> String{
+        self.summary_for(self.rows.len())
+    }
+    pub fn summary_for(&self,rows: usize)-> String{
+        let query: String= self.query
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+        let query= match query.char_indices().nth(40){
+            Some((index,_))=> format!("{}...",&query[..index]),
+            None=> query
+        };
+        let mut summary= format!("{}: ",query);
+        if self.headers.is_empty(){
+            summary.push_str(&format!("{} rows affected",self.affected_rows));
+        }else {
+            summary.push_str(&match self.fetch{
+                Fetch::Done=> format!("{} rows returned",rows),
+                Fetch::More=> format!("first {} rows fetched",rows),
+                Fetch::Truncated=> format!("{} rows returned, max rows reached",rows),
+                Fetch::Stopped=> format!("{} rows fetched, stopped",rows)
+            });
+        }
+        if let Some(id)= self.last_insert_id{
+            summary.push_str(&format!(", last insert id {}",id));
+        }
+        if !self.warnings.is_empty(){
+            summary.push_str(&format!(", {} warnings",self.warnings.len()));
+        }
+        summary.push_str(&format!(" in {:.3}s\n",self.elapsed.as_secs_f32()));
+        for warning in &self.warnings{
+            summary.push_str(&format!("  {}\n",warning));
+        }
+        summary
+    }
+}
+
+#[derive(Debug,Default,Clone)]
+pub struct ColumnInfo{
+    pub name: String,
+    pub kind: String,
+    pub nullable: bool,
+    pub default: Option<String>,
+    pub key: String,
+    pub comment: String
+}
+
+#[derive(Debug,Default)]
+pub struct TableInfo{
+    pub schema: String,
+    pub name: String,
+    pub columns: Vec<ColumnInfo>,
+    pub indexes: Vec<String>,
+    pub foreign_keys: Vec<String>,
+    pub ddl: String
+}
+
+impl TableInfo{
+    pub fn primary_key(&self)-> Vec<String>{
+        self.columns
+            .iter()
+            .filter(|column| column.key== "PRI")
+            .map(|column| column.name.clone())
+            .collect()
+    }
+}
+
+pub trait Sink{
+    fn columns(&mut self,headers: &[String],types: &[String])-> AppResult<()>;
+    fn row(&mut self,row: Vec<Value>)-> AppResult<bool>;
+}
+
+impl Sink for QueryResult{
+    fn columns(&mut self,headers: &[String],types: &[String])-> AppResult<()>{
+        self.headers= headers.to_vec();
+        self.types= types.to_vec();
+        Ok(())
+    }
+    fn row(&mut self,row: Vec<Value>)-> AppResult<bool>{
+        self.rows.push(row);
+        Ok(true)
+    }
+}
+
+pub trait Cancel: Send{
+    fn cancel(&self)-> AppResult<()>;
+}
+
+pub trait Database: Send{
+    fn connect(info: &DatabaseInfo)-> AppResult<Self>
+    where
+        Self: Sized;
+    fn query(&mut self,query: &str,sink: &mut dyn Sink)-> AppResult<QueryResult>;
+    fn execute(&mut self,query: &str)-> AppResult<QueryResult>{
+        let mut rows= QueryResult::default();
+        let mut result= self.query(query,&mut rows)?;
+        result.rows= rows.rows;
+        Ok(result)
+    }
+    fn ping(&mut self)-> bool;
+    fn cancel_handle(&self)-> Box<dyn Cancel>;
+    fn use_schema(&mut self,schema: &str)-> AppResult<()>;
+    fn set_read_only(&mut self)-> AppResult<()>;
+    fn schemas(&mut self)-> AppResult<Vec<String>>;
+    fn encryption(&self)-> Option<String>;
+    fn tables(&mut self,schema: &str)-> AppResult<Vec<(String,bool)>>;
+    fn columns(&mut self,schema: &str,table: &str)-> AppResult<Vec<String>>;
+    fn indexes(&mut self,schema: &str,table: &str)-> AppResult<Vec<String>>;
+    fn triggers(&mut self,schema: &str,table: &str)-> AppResult<Vec<String>>;
+    fn describe_table(&mut self,schema: &str,table: &str)-> AppResult<TableInfo>;
+    fn quote_identifier(&self,name: &str)-> String;
+    fn current_schema(&mut self)-> AppResult<String>;
+}
+
+pub fn transaction(db: &mut dyn Database,statements: &[String])-> AppResult<()>{
+    db.execute("BEGIN")?;
+    for statement in statements{
+        let result= db.execute(statement).and_then(|result| match result.matched_rows.unwrap_or(result.affected_rows){
+            1=> Ok(()),
+            count=> Err(format!("{} changed {} rows instead of 1",statement,count).into())
+        });
+        if let Err(err)= result{
+            let _= db.execute("ROLLBACK");
+            return Err(err);
+        }
+    }
+    db.execute("COMMIT")?;
+    Ok(())
+}
+
+pub fn connect(info: &DatabaseInfo)-> AppResult<Box<dyn Database>>{
+    let mut conn: Box<dyn Database>= match info.engine(){
+        Engine::Mysql=> Box::new(self::mysql::MysqlDatabase::connect(info)?),
+        Engine::Postgres=> Box::new(self::postgres::PostgresDatabase::connect(info)?),
+        Engine::Sqlite=> Box::new(sqlite::SqliteDatabase::connect(info)?)
+    };
+    if let Some(schema)= &info.schema{
+        conn.use_schema(schema)?;
+    }
+    if info.read_only{
+        conn.set_read_only()?;
+    }
+    Ok(conn)
+}
@@ -0,0 +1,362 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs::{self, DirBuilder, OpenOptions};
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+use std::path::PathBuf;
+use std::process;
+use std::sync::Arc;
+use mysql::*;
+use openssl::pkcs12::Pkcs12;
+use openssl::pkey::PKey;
+use openssl::rand::rand_bytes;
+use openssl::stack::Stack;
+use openssl::x509::X509;
+use mysql::prelude::*;
+use mysql::consts::{ColumnFlags, ColumnType};
+
+use crate::app::{AppResult, DatabaseInfo, Engine};
+use super::{Cancel, ColumnInfo, Database, QueryResult, Sink, TableInfo, Value};
+
+pub struct MysqlDatabase{
+    conn: Conn,
+    opts: Opts,
+    identity: Option<Arc<TempIdentity>>,
+    cipher: Option<String>
+}
+
+struct MysqlCancel{
+    opts: Opts,
+    _identity: Option<Arc<TempIdentity>>,
+    id: u32
+}
+
+struct TempIdentity{
+    dir: PathBuf
+}
+
+impl Drop for TempIdentity{
+    fn drop(&mut self){
+        let _= fs::remove_dir_all(&self.dir);
+    }
+}
+
+impl Cancel for MysqlCancel{
+    fn cancel(&self)-> AppResult<()>{
+        let mut conn= Conn::new(self.opts.clone())?;
+        conn.query_drop(format!("KILL QUERY {}",self.id))?;
+        Ok(())
+    }
+}
+
+impl Database for MysqlDatabase{
+    fn connect(info: &DatabaseInfo)-> AppResult<Self>{
+        let (builder,identity)= info.build_opts()?;
+        let mut opts= Opts::from(builder);
+        let mut conn= match Conn::new(opts.clone()){
+            Err(Error::DriverError(DriverError::TlsNotSupported)) if info.tls_mode()== "preferred"=> {
+                opts= Opts::from(OptsBuilder::from_opts(opts).ssl_opts(None::<SslOpts>));
+                Conn::new(opts.clone())?
+            },
+            conn=> conn?
+        };
+        let cipher= conn.query_first("SHOW SESSION STATUS LIKE 'Ssl_cipher'")?
+            .map(|(_,cipher): (String,String)| cipher)
+            .filter(|cipher| !cipher.is_empty());
+        Ok(Self{conn,opts,identity: identity.map(Arc::new),cipher})
+    }
+    fn query(&mut self,query: &str,sink: &mut dyn Sink)-> AppResult<QueryResult>{
+        let id= self.conn.connection_id();
+        let mut result= self.conn.query_iter(query)?;
+        let columns: Vec<Column>= result.columns().as_ref().to_vec();
+        let headers: Vec<String>= columns
+            .iter()
+            .map(|column| column.name_str().to_string())
+            .collect();
+        let types: Vec<String>= columns
+            .iter()
+            .map(|column| format!("{:?}",column.column_type())
+                 .trim_start_matches("MYSQL_TYPE_")
+                 .to_lowercase())
+            .collect();
+        if !headers.is_empty(){
+            sink.columns(&headers,&types)?;
+        }
+        for row in result.by_ref(){
+            let mut item: Vec<Value>= Vec::new();
+            for (value,column) in row?.unwrap().into_iter().zip(&columns){
+                item.push(to_value(value,column));
+            }
+            if !sink.row(item)?{
+                let _= MysqlCancel{opts: self.opts.clone(),_identity: self.identity.clone(),id}.cancel();
+                break;
+            }
+        }
+        drop(result);
+        let affected_rows= self.conn.affected_rows();
+        let matched_rows= matched_rows(&self.conn.info_str());
+        let last_insert_id= match self.conn.last_insert_id(){
+            0=> None,
+            id=> Some(id)
+        };
+        let warnings= match self.conn.warnings(){
+            0=> Vec::new(),
+            _=> self.conn.query_map("SHOW WARNINGS",
+                |(level,code,message): (String,u32,String)| {
+                    format!("{} {}: {}",level,code,message)
+                })?
+        };
+        Ok(QueryResult{
+            headers,
+            types,
+            affected_rows,
+            matched_rows,
+            last_insert_id,
+            warnings,
+            ..Default::default()
+        })
+    }
+    fn ping(&mut self)-> bool{
+        self.conn.ping()
+    }
+    fn cancel_handle(&self)-> Box<dyn Cancel>{
+        Box::new(MysqlCancel{
+            opts: self.opts.clone(),
+            _identity: self.identity.clone(),
+            id: self.conn.connection_id()
+        })
+    }
+    fn use_schema(&mut self,schema: &str)-> AppResult<()>{
+        self.conn.query_drop(format!("USE `{}`",schema.replace('`',"``")))?;
+        Ok(())
+    }
+    fn set_read_only(&mut self)-> AppResult<()>{
+        self.conn.query_drop("SET SESSION TRANSACTION READ ONLY")?;
+        Ok(())
+    }
+    fn schemas(&mut self)-> AppResult<Vec<String>>{
+        Ok(self.conn.query("SHOW DATABASES")?)
+    }
+    fn encryption(&self)-> Option<String>{
+        self.cipher.clone()
+    }
+    fn tables(&mut self,schema: &str)-> AppResult<Vec<(String,bool)>>{
+        Ok(self.conn.exec_map(
+                "SELECT TABLE_NAME, TABLE_TYPE FROM information_schema.TABLES \
+                WHERE TABLE_SCHEMA = ? ORDER BY TABLE_NAME",
+                (schema,),
+                |(name,kind): (String,String)| (name,kind== "VIEW"))?)
+    }
+    fn columns(&mut self,schema: &str,table: &str)-> AppResult<Vec<String>>{
+        Ok(self.conn.exec_map(
+                "SELECT COLUMN_NAME, COLUMN_TYPE FROM information_schema.COLUMNS \
+                WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? ORDER BY ORDINAL_POSITION",
+                (schema,table),
+                |(name,kind): (String,String)| format!("{} {}",name,kind))?)
+    }
+    fn indexes(&mut self,schema: &str,table: &str)-> AppResult<Vec<String>>{
+        Ok(self.conn.exec_map(
+                "SELECT INDEX_NAME, GROUP_CONCAT(COLUMN_NAME ORDER BY SEQ_IN_INDEX SEPARATOR ', '), \
+                IF(MIN(NON_UNIQUE) = 0, ' unique', '') FROM information_schema.STATISTICS \
+                WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? GROUP BY INDEX_NAME ORDER BY INDEX_NAME",
+                (schema,table),
+                |(name,columns,unique): (String,String,String)| {
+                    format!("{} ({}){}",name,columns,unique)
+                })?)
+    }
+    fn triggers(&mut self,schema: &str,table: &str)-> AppResult<Vec<String>>{
+        Ok(self.conn.exec_map(
+                "SELECT TRIGGER_NAME, ACTION_TIMING, EVENT_MANIPULATION FROM information_schema.TRIGGERS \
+                WHERE EVENT_OBJECT_SCHEMA = ? AND EVENT_OBJECT_TABLE = ? ORDER BY TRIGGER_NAME",
+                (schema,table),
+                |(name,timing,event): (String,String,String)| {
+                    format!("{} {} {}",name,timing,event)
+                })?)
+    }
+    fn describe_table(&mut self,schema: &str,table: &str)-> AppResult<TableInfo>{
+        let columns= self.conn.exec_map(
+            "SELECT COLUMN_NAME, COLUMN_TYPE, IS_NULLABLE, COLUMN_DEFAULT, COLUMN_KEY, COLUMN_COMMENT \
+            FROM information_schema.COLUMNS \
+            WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? ORDER BY ORDINAL_POSITION",
+            (schema,table),
+            |(name,kind,nullable,default,key,comment):
+             (String,String,String,Option<String>,String,String)| {
+                ColumnInfo{name,kind,nullable: nullable== "YES",default,key,comment}
+            })?;
+        let foreign_keys= self.conn.exec_map(
+            "SELECT CONSTRAINT_NAME, COLUMN_NAME, REFERENCED_TABLE_SCHEMA, \
+            REFERENCED_TABLE_NAME, REFERENCED_COLUMN_NAME FROM information_schema.KEY_COLUMN_USAGE \
+            WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? AND REFERENCED_TABLE_NAME IS NOT NULL \
+            ORDER BY CONSTRAINT_NAME, ORDINAL_POSITION",
+            (schema,table),
+            |(name,column,ref_schema,ref_table,ref_column): (String,String,String,String,String)| {
+                format!("{}: {} -> {}.{}({})",name,column,ref_schema,ref_table,ref_column)
+            })?;
+        let ddl= self.conn.query_first::<Row,_>(format!("SHOW CREATE TABLE {}.{}",
+                                       self.quote_identifier(schema),
+                                       self.quote_identifier(table)))?
+            .and_then(|row| row.get::<String,_>(1))
+            .unwrap_or_default();
+        Ok(TableInfo{
+            schema: schema.to_string(),
+            name: table.to_string(),
+            columns,
+            indexes: self.indexes(schema,table)?,
+            foreign_keys,
+            ddl
+        })
+    }
+    fn quote_identifier(&self,name: &str)-> String{
+        Engine::Mysql.quote_identifier(name)
+    }
+    fn current_schema(&mut self)-> AppResult<String>{
+        let schema: Option<Option<String>>= self.conn.query_first("SELECT DATABASE()")?;
+        schema.flatten().ok_or_else(|| "no database selected".into())
+    }
+}
+
+fn matched_rows(info: &str)-> Option<u64>{
+    info.strip_prefix("Rows matched: ")?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+fn to_value(value: mysql::Value,column: &Column)-> Value{
+    let bytes= match value{
+        mysql::Value::NULL=> return Value::Null,
+        mysql::Value::Int(value)=> return Value::Int(value),
+        mysql::Value::UInt(value)=> return Value::UInt(value),
+        mysql::Value::Float(value)=> return Value::Float(value as f64),
+        mysql::Value::Double(value)=> return Value::Float(value),
+        mysql::Value::Bytes(bytes)=> bytes,
+        value=> return Value::DateTime(value.as_sql(true).trim_matches('\'').to_string())
+    };
+    let text= || Value::Text(String::from_utf8_lossy(&bytes).to_string());
+    let parsed= String::from_utf8(bytes.clone()).ok();
+    let unsigned= column.flags().contains(ColumnFlags::UNSIGNED_FLAG);
+    match column.column_type(){
+        ColumnType::MYSQL_TYPE_TINY| ColumnType::MYSQL_TYPE_SHORT| ColumnType::MYSQL_TYPE_INT24|
+        ColumnType::MYSQL_TYPE_LONG| ColumnType::MYSQL_TYPE_LONGLONG| ColumnType::MYSQL_TYPE_YEAR=> {
+            match (unsigned,parsed){
+                (true,Some(value))=> value.parse().map_or_else(|_| text(),Value::UInt),
+                (false,Some(value))=> value.parse().map_or_else(|_| text(),Value::Int),
+                _=> text()
+            }
+        },
+        ColumnType::MYSQL_TYPE_FLOAT| ColumnType::MYSQL_TYPE_DOUBLE=> {
+            parsed.and_then(|value| value.parse().ok()).map_or_else(text,Value::Float)
+        },
+        ColumnType::MYSQL_TYPE_DECIMAL| ColumnType::MYSQL_TYPE_NEWDECIMAL=> {
+            parsed.map_or_else(text,Value::Decimal)
+        },
+        ColumnType::MYSQL_TYPE_DATE| ColumnType::MYSQL_TYPE_NEWDATE| ColumnType::MYSQL_TYPE_TIME|
+        ColumnType::MYSQL_TYPE_TIME2| ColumnType::MYSQL_TYPE_DATETIME| ColumnType::MYSQL_TYPE_DATETIME2|
+        ColumnType::MYSQL_TYPE_TIMESTAMP| ColumnType::MYSQL_TYPE_TIMESTAMP2=> {
+            parsed.map_or_else(text,Value::DateTime)
+        },
+        ColumnType::MYSQL_TYPE_JSON=> parsed.map_or_else(text,Value::Text),
+        ColumnType::MYSQL_TYPE_BIT| ColumnType::MYSQL_TYPE_GEOMETRY=> Value::Bytes(bytes),
+        _ if column.character_set()== 63=> Value::Bytes(bytes),
+        _=> parsed.map_or_else(text,Value::Text)
+    }
+}
+
+impl DatabaseInfo{
+    fn build_opts(&self)-> AppResult<(OptsBuilder,Option<TempIdentity>)>{
+        let user= self.user.clone();
+        let password= self.password.clone();
+        let host= self.host.clone();
+        let database= self.database.clone();
+        let params: HashMap<String,String>= self.params
+            .iter()
+            .map(|(key,value)| (key.replace('-',"_"),value.clone()))
+            .collect();
+        let builder= OptsBuilder::new()
+            .from_hash_map(&params)?
+            .user(Some(user))
+            .pass(Some(password))
+            .db_name(Some(database));
+        let (ssl,identity)= match self.ssl_opts()?{
+            Some((ssl,identity))=> (Some(ssl),identity),
+            None=> (None,None)
+        };
+        let builder= builder.ssl_opts(ssl);
+        Ok((match self.socket_path(){
+            Some(socket)=> builder.socket(Some(socket)),
+            None=> builder.ip_or_hostname(Some(host)).tcp_port(self.port())
+        },identity))
+    }
+    fn tls_mode(&self)-> String{
+        match &self.ssl_mode{
+            Some(mode)=> mode.to_lowercase().replace('_',"-"),
+            None if self.ssl_ca.is_some()|| self.ssl_cert.is_some()=> String::from("verify-ca"),
+            None=> String::from("disabled")
+        }
+    }
+    fn ssl_opts(&self)-> AppResult<Option<(SslOpts,Option<TempIdentity>)>>{
+        let mode= self.tls_mode();
+        let opts= match mode.as_str(){
+            "disabled"=> return Ok(None),
+            "preferred"| "required"=> SslOpts::default()
+                .with_danger_accept_invalid_certs(true)
+                .with_danger_skip_domain_validation(true),
+            "verify-ca"=> SslOpts::default()
+                .with_danger_skip_domain_validation(true),
+            "verify-identity"=> SslOpts::default(),
+            _=> return Err(format!(
+                    "unknown ssl mode '{}' (disabled, preferred, required, verify-ca, verify-identity)",
+                    mode).into())
+        };
+        let opts= match &self.ssl_ca{
+            Some(ca)=> opts.with_root_cert_path(Some(std::path::PathBuf::from(ca))),
+            None=> opts
+        };
+        Ok(Some(match self.client_identity()?{
+            Some((client,identity))=> (opts.with_client_identity(Some(client)),Some(identity)),
+            None=> (opts,None)
+        }))
+    }
+    fn client_identity(&self)-> AppResult<Option<(ClientIdentity,TempIdentity)>>{
+        let (cert,key)= match (&self.ssl_cert,&self.ssl_key){
+            (None,None)=> return Ok(None),
+            (None,Some(_))=> return Err("--ssl-key needs --ssl-cert".into()),
+            (Some(_),None)=> return Err("--ssl-cert needs --ssl-key".into()),
+            (Some(cert),Some(key))=> (cert,key)
+        };
+        let mut chain= X509::stack_from_pem(&fs::read(cert)
+            .map_err(|err| format!("{}: {}",cert,err))?)?.into_iter();
+        let key= PKey::private_key_from_pem(&fs::read(key)
+            .map_err(|err| format!("{}: {}",key,err))?)?;
+        let cert= chain.next().ok_or(format!("{}: no certificate found",cert))?;
+        let mut ca= Stack::new()?;
+        for extra in chain{
+            ca.push(extra)?;
+        }
+        let mut secret= [0u8;16];
+        rand_bytes(&mut secret)?;
+        let password: String= secret.iter().map(|byte| format!("{:02x}",byte)).collect();
+        let archive= Pkcs12::builder()
+            .name("sqltui")
+            .pkey(&key)
+            .cert(&cert)
+            .ca(ca)
+            .build2(&password)?;
+        rand_bytes(&mut secret)?;
+        let suffix: String= secret.iter().map(|byte| format!("{:02x}",byte)).collect();
+        let identity= TempIdentity{dir: env::temp_dir().join(format!("sqltui-{}-{}",process::id(),suffix))};
+        let mut dir= DirBuilder::new();
+        #[cfg(unix)]
+        dir.mode(0o700);
+        dir.create(&identity.dir)?;
+        let path= identity.dir.join("client.p12");
+        let mut file= OpenOptions::new();
+        file.write(true).create_new(true);
+        #[cfg(unix)]
+        file.mode(0o600);
+        file.open(&path)?.write_all(&archive.to_der()?)?;
+        Ok(Some((ClientIdentity::new(path).with_password(password),identity)))
+    }
+}
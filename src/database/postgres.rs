@@ -0,0 +1,314 @@
+use postgres::{CancelToken, Client, Config, NoTls, SimpleQueryMessage, SimpleQueryRow};
+use postgres::types::Type;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::app::{AppResult, DatabaseInfo, Engine};
+use crate::sql;
+use super::{unhex, Cancel, ColumnInfo, Database, QueryResult, Sink, TableInfo, Value};
+
+const CURSOR: &str= "sqltui_cursor";
+const CURSOR_ROWS: usize= 1000;
+
+pub struct PostgresDatabase{
+    client: Client,
+    notices: Arc<Mutex<Vec<String>>>,
+    transaction: bool
+}
+
+impl Cancel for CancelToken{
+    fn cancel(&self)-> AppResult<()>{
+        self.cancel_query(NoTls)?;
+        Ok(())
+    }
+}
+
+impl Database for PostgresDatabase{
+    fn connect(info: &DatabaseInfo)-> AppResult<Self>{
+        let params: Vec<String>= info.params
+            .iter()
+            .map(|(key,value)| format!("{}='{}'",key,value.replace('\\',"\\\\").replace('\'',"\\'")))
+            .collect();
+        let mut config: Config= match params.is_empty(){
+            true=> Config::new(),
+            false=> params.join(" ").parse()?
+        };
+        config
+            .user(&info.user)
+            .password(&info.password)
+            .host(&info.host)
+            .port(info.port());
+        if !info.database.is_empty() {
+            config.dbname(&info.database);
+        }
+        let notices= Arc::new(Mutex::new(Vec::new()));
+        let sink= Arc::clone(&notices);
+        config.notice_callback(move |notice| {
+            sink.lock()
+                .expect("notices poisoned")
+                .push(format!("{} {}: {}",notice.severity(),notice.code().code(),notice.message()));
+        });
+        let client= config.connect(NoTls)?;
+        Ok(Self{client,notices,transaction: false})
+    }
+    fn query(&mut self,query: &str,sink: &mut dyn Sink)-> AppResult<QueryResult>{
+        self.notices.lock().expect("notices poisoned").clear();
+        let result= match sql::streamable(query){
+            true=> self.stream(query,sink),
+            false=> self.simple(query,sink)
+        };
+        match (sql::transaction_state(query),&result){
+            (Some(true),Err(_))| (None,_)=> {},
+            (Some(state),_)=> self.transaction= state
+        }
+        let mut result= result?;
+        result.warnings= std::mem::take(&mut *self.notices.lock().expect("notices poisoned"));
+        Ok(result)
+    }
+    fn ping(&mut self)-> bool{
+        self.client.is_valid(Duration::from_secs(5)).is_ok()
+    }
+    fn cancel_handle(&self)-> Box<dyn Cancel>{
+        Box::new(self.client.cancel_token())
+    }
+    fn use_schema(&mut self,schema: &str)-> AppResult<()>{
+        self.client.batch_execute(
+            &format!("SET search_path TO \"{}\"",schema.replace('"',"\"\"")))?;
+        Ok(())
+    }
+    fn set_read_only(&mut self)-> AppResult<()>{
+        self.client.batch_execute("SET default_transaction_read_only = on")?;
+        Ok(())
+    }
+    fn schemas(&mut self)-> AppResult<Vec<String>>{
+        let rows= self.client.query(
+            "SELECT schema_name::text FROM information_schema.schemata",&[])?;
+        Ok(rows.iter().map(|row| row.get(0)).collect())
+    }
+    fn encryption(&self)-> Option<String>{
+        None
+    }
+    fn tables(&mut self,schema: &str)-> AppResult<Vec<(String,bool)>>{
+        let rows= self.client.query(
+            "SELECT table_name::text, table_type::text FROM information_schema.tables \
+            WHERE table_schema = $1 ORDER BY table_name",&[&schema])?;
+        Ok(rows.iter().map(|row| (row.get(0),row.get::<_,String>(1)== "VIEW")).collect())
+    }
+    fn columns(&mut self,schema: &str,table: &str)-> AppResult<Vec<String>>{
+        let rows= self.client.query(
+            "SELECT column_name::text, data_type::text FROM information_schema.columns \
+            WHERE table_schema = $1 AND table_name = $2 ORDER BY ordinal_position",
+            &[&schema,&table])?;
+        Ok(rows.iter()
+           .map(|row| format!("{} {}",row.get::<_,String>(0),row.get::<_,String>(1)))
+           .collect())
+    }
+    fn indexes(&mut self,schema: &str,table: &str)-> AppResult<Vec<String>>{
+        let rows= self.client.query(
+            "SELECT indexname::text, substring(indexdef from 'USING .*') FROM pg_indexes \
+            WHERE schemaname = $1 AND tablename = $2 ORDER BY indexname",
+            &[&schema,&table])?;
+        Ok(rows.iter()
+           .map(|row| format!("{} {}",row.get::<_,String>(0),row.get::<_,String>(1)))
+           .collect())
+    }
+    fn triggers(&mut self,schema: &str,table: &str)-> AppResult<Vec<String>>{
+        let rows= self.client.query(
+            "SELECT trigger_name::text, action_timing::text, \
+            string_agg(event_manipulation::text, ' OR ') FROM information_schema.triggers \
+            WHERE event_object_schema = $1 AND event_object_table = $2 \
+            GROUP BY 1, 2 ORDER BY 1",
+            &[&schema,&table])?;
+        Ok(rows.iter()
+           .map(|row| format!("{} {} {}",row.get::<_,String>(0),
+                              row.get::<_,String>(1),row.get::<_,String>(2)))
+           .collect())
+    }
+    fn describe_table(&mut self,schema: &str,table: &str)-> AppResult<TableInfo>{
+        let relation= format!("{}.{}",self.quote_identifier(schema),self.quote_identifier(table));
+        let columns: Vec<ColumnInfo>= self.client.query(
+            "SELECT a.attname::text, format_type(a.atttypid, a.atttypmod), NOT a.attnotnull, \
+            pg_get_expr(d.adbin, d.adrelid), \
+            COALESCE((SELECT CASE c.contype WHEN 'p' THEN 'PRI' ELSE 'UNI' END FROM pg_constraint c \
+                WHERE c.conrelid = a.attrelid AND a.attnum = ANY(c.conkey) AND c.contype IN ('p', 'u') \
+                ORDER BY c.contype LIMIT 1), ''), \
+            COALESCE(col_description(a.attrelid, a.attnum), '') \
+            FROM pg_attribute a LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum \
+            WHERE a.attrelid = $1::text::regclass AND a.attnum > 0 AND NOT a.attisdropped \
+            ORDER BY a.attnum",&[&relation])?
+            .iter()
+            .map(|row| ColumnInfo{
+                name: row.get(0),
+                kind: row.get(1),
+                nullable: row.get(2),
+                default: row.get(3),
+                key: row.get(4),
+                comment: row.get(5)
+            })
+            .collect();
+        let constraints: Vec<(String,String,i8)>= self.client.query(
+            "SELECT conname::text, pg_get_constraintdef(oid), contype::\"char\" FROM pg_constraint \
+            WHERE conrelid = $1::text::regclass ORDER BY contype, conname",&[&relation])?
+            .iter()
+            .map(|row| (row.get(0),row.get(1),row.get(2)))
+            .collect();
+        let view: Option<String>= self.client.query_one(
+            "SELECT CASE WHEN c.relkind IN ('v', 'm') THEN pg_get_viewdef(c.oid) END \
+            FROM pg_class c WHERE c.oid = $1::text::regclass",&[&relation])?
+            .get(0);
+        let ddl= match view{
+            Some(definition)=> format!("CREATE VIEW {} AS\n{}",relation,definition),
+            None=> {
+                let mut lines: Vec<String>= columns.iter().map(|column| {
+                    let mut line= format!("  {} {}",self.quote_identifier(&column.name),column.kind);
+                    if !column.nullable{
+                        line.push_str(" NOT NULL");
+                    }
+                    if let Some(default)= &column.default{
+                        line.push_str(&format!(" DEFAULT {}",default));
+                    }
+                    line
+                }).collect();
+                for (name,definition,_) in &constraints{
+                    lines.push(format!("  CONSTRAINT {} {}",self.quote_identifier(name),definition));
+                }
+                let mut ddl= format!("CREATE TABLE {} (\n{}\n);",relation,lines.join(",\n"));
+                for row in self.client.query(
+                    "SELECT pg_get_indexdef(i.indexrelid) FROM pg_index i \
+                    WHERE i.indrelid = $1::text::regclass AND NOT EXISTS \
+                    (SELECT 1 FROM pg_constraint c WHERE c.conindid = i.indexrelid)",&[&relation])?{
+                    ddl.push_str(&format!("\n{};",row.get::<_,String>(0)));
+                }
+                ddl
+            }
+        };
+        Ok(TableInfo{
+            schema: schema.to_string(),
+            name: table.to_string(),
+            columns,
+            indexes: self.indexes(schema,table)?,
+            foreign_keys: constraints
+                .into_iter()
+                .filter(|(_,_,kind)| *kind== b'f' as i8)
+                .map(|(name,definition,_)| format!("{}: {}",name,definition))
+                .collect(),
+            ddl
+        })
+    }
+    fn quote_identifier(&self,name: &str)-> String{
+        Engine::Postgres.quote_identifier(name)
+    }
+    fn current_schema(&mut self)-> AppResult<String>{
+        let schema: Option<String>= self.client.query_one("SELECT current_schema()::text",&[])?.get(0);
+        schema.ok_or_else(|| "no schema in the search path".into())
+    }
+}
+
+impl PostgresDatabase{
+    fn stream(&mut self,query: &str,sink: &mut dyn Sink)-> AppResult<QueryResult>{
+        let begin= !self.transaction;
+        if begin{
+            self.client.batch_execute("BEGIN")?;
+        }
+        let fetched= self.client
+            .batch_execute(&format!("DECLARE {} NO SCROLL CURSOR FOR {}",
+                                    CURSOR,query.trim().trim_end_matches(';')))
+            .map_err(Into::into)
+            .and_then(|_| self.fetch_cursor(sink));
+        let closed= match begin{
+            true=> self.client.batch_execute("COMMIT"),
+            false=> self.client.batch_execute(&format!("CLOSE {}",CURSOR))
+        };
+        let result= fetched?;
+        closed?;
+        Ok(result)
+    }
+    fn simple(&mut self,query: &str,sink: &mut dyn Sink)-> AppResult<QueryResult>{
+        let mut result= QueryResult::default();
+        let mut types: Vec<Type>= Vec::new();
+        let mut open= true;
+        for message in self.client.simple_query(query)?{
+            match message{
+                SimpleQueryMessage::RowDescription(columns)=> {
+                    result.headers= columns.iter().map(|column| column.name().to_string()).collect();
+                    types= vec![Type::TEXT;columns.len()];
+                    result.types= types.iter().map(|kind| kind.name().to_string()).collect();
+                    sink.columns(&result.headers,&result.types)?;
+                },
+                SimpleQueryMessage::Row(row) if open=> {
+                    open= sink.row(row_values(&row,&types)?)?;
+                },
+                SimpleQueryMessage::CommandComplete(count) if result.headers.is_empty()=> {
+                    result.affected_rows= count;
+                },
+                _=> {}
+            }
+        }
+        Ok(result)
+    }
+    fn fetch_cursor(&mut self,sink: &mut dyn Sink)-> AppResult<QueryResult>{
+        let fetch= format!("FETCH {} FROM {}",CURSOR_ROWS,CURSOR);
+        let stmt= self.client.prepare(&fetch)?;
+        let headers: Vec<String>= stmt.columns()
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect();
+        let types: Vec<Type>= stmt.columns()
+            .iter()
+            .map(|column| column.type_().clone())
+            .collect();
+        let names: Vec<String>= types.iter().map(|kind| kind.name().to_string()).collect();
+        if !headers.is_empty(){
+            sink.columns(&headers,&names)?;
+        }
+        let result= QueryResult{
+            headers,
+            types: names,
+            ..Default::default()
+        };
+        loop{
+            let mut count= 0;
+            for message in self.client.simple_query(&fetch)?{
+                if let SimpleQueryMessage::Row(row)= message{
+                    count+= 1;
+                    if !sink.row(row_values(&row,&types)?)?{
+                        return Ok(result);
+                    }
+                }
+            }
+            if count< CURSOR_ROWS{
+                return Ok(result);
+            }
+        }
+    }
+}
+
+fn row_values(row: &SimpleQueryRow,types: &[Type])-> AppResult<Vec<Value>>{
+    let mut item: Vec<Value>= Vec::new();
+    for (index,kind) in types.iter().enumerate(){
+        item.push(to_value(row.try_get(index)?,kind));
+    }
+    Ok(item)
+}
+
+fn to_value(value: Option<&str>,kind: &Type)-> Value{
+    let value= match value{
+        Some(value)=> value,
+        None=> return Value::Null
+    };
+    let text= || Value::Text(value.to_string());
+    match *kind{
+        Type::BOOL=> Value::Bool(value== "t"),
+        Type::INT2| Type::INT4| Type::INT8=> value.parse().map_or_else(|_| text(),Value::Int),
+        Type::OID=> value.parse().map_or_else(|_| text(),Value::UInt),
+        Type::FLOAT4| Type::FLOAT8=> value.parse().map_or_else(|_| text(),Value::Float),
+        Type::NUMERIC=> Value::Decimal(value.to_string()),
+        Type::DATE| Type::TIME| Type::TIMETZ| Type::TIMESTAMP| Type::TIMESTAMPTZ| Type::INTERVAL=> {
+            Value::DateTime(value.to_string())
+        },
+        Type::BYTEA=> value.strip_prefix("\\x")
+            .and_then(unhex)
+            .map_or_else(text,Value::Bytes),
+        _=> text()
+    }
+}
@@ -0,0 +1,185 @@
+use rusqlite::{Connection, InterruptHandle};
+use rusqlite::types::ValueRef;
+
+use crate::app::{AppResult, DatabaseInfo, Engine};
+use super::{Cancel, ColumnInfo, Database, QueryResult, Sink, TableInfo, Value};
+
+pub struct SqliteDatabase{
+    conn: Connection
+}
+
+impl Cancel for InterruptHandle{
+    fn cancel(&self)-> AppResult<()>{
+        self.interrupt();
+        Ok(())
+    }
+}
+
+impl Database for SqliteDatabase{
+    fn connect(info: &DatabaseInfo)-> AppResult<Self>{
+        let path= info.sqlite.clone().unwrap_or_default();
+        let conn= Connection::open(path)?;
+        Ok(Self{conn})
+    }
+    fn query(&mut self,query: &str,sink: &mut dyn Sink)-> AppResult<QueryResult>{
+        let mut stmt= self.conn.prepare(query)?;
+        let headers: Vec<String>= stmt.column_names()
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        if headers.is_empty() {
+            let affected_rows= stmt.execute([])? as u64;
+            let keyword= query.split_whitespace().next().unwrap_or_default();
+            let last_insert_id= match affected_rows> 0&&
+                (keyword.eq_ignore_ascii_case("insert")|| keyword.eq_ignore_ascii_case("replace")){
+                true=> Some(self.conn.last_insert_rowid() as u64),
+                false=> None
+            };
+            return Ok(QueryResult{
+                headers,
+                affected_rows,
+                last_insert_id,
+                ..Default::default()
+            });
+        }
+        let types: Vec<String>= stmt.columns()
+            .iter()
+            .map(|column| column.decl_type().unwrap_or_default().to_string())
+            .collect();
+        sink.columns(&headers,&types)?;
+        let mut result= stmt.query([])?;
+        while let Some(row)= result.next()? {
+            let mut item: Vec<Value>= Vec::new();
+            for index in 0..headers.len(){
+                item.push(to_value(row.get_ref(index)?));
+            }
+            if !sink.row(item)?{
+                break;
+            }
+        }
+        Ok(QueryResult{
+            headers,
+            types,
+            ..Default::default()
+        })
+    }
+    fn ping(&mut self)-> bool{
+        true
+    }
+    fn cancel_handle(&self)-> Box<dyn Cancel>{
+        Box::new(self.conn.get_interrupt_handle())
+    }
+    fn use_schema(&mut self,_schema: &str)-> AppResult<()>{
+        Ok(())
+    }
+    fn set_read_only(&mut self)-> AppResult<()>{
+        self.conn.execute_batch("PRAGMA query_only = ON")?;
+        Ok(())
+    }
+    fn schemas(&mut self)-> AppResult<Vec<String>>{
+        let mut stmt= self.conn.prepare("PRAGMA database_list")?;
+        let names= stmt.query_map([],|row| row.get::<_,String>(1))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(names)
+    }
+    fn encryption(&self)-> Option<String>{
+        None
+    }
+    fn tables(&mut self,schema: &str)-> AppResult<Vec<(String,bool)>>{
+        let mut stmt= self.conn.prepare(&format!(
+                "SELECT name, type FROM {}.sqlite_master \
+                WHERE type IN ('table', 'view') ORDER BY name",
+                self.quote_identifier(schema)))?;
+        let tables= stmt.query_map([],|row| {
+            Ok((row.get::<_,String>(0)?,row.get::<_,String>(1)?== "view"))
+        })?.collect::<rusqlite::Result<Vec<(String,bool)>>>()?;
+        Ok(tables)
+    }
+    fn columns(&mut self,schema: &str,table: &str)-> AppResult<Vec<String>>{
+        let mut stmt= self.conn.prepare(
+            "SELECT name, type FROM pragma_table_info(?1, ?2)")?;
+        let columns= stmt.query_map([table,schema],|row| {
+            Ok(format!("{} {}",row.get::<_,String>(0)?,row.get::<_,String>(1)?))
+        })?.collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(columns)
+    }
+    fn indexes(&mut self,schema: &str,table: &str)-> AppResult<Vec<String>>{
+        let mut stmt= self.conn.prepare(
+            "SELECT list.name, group_concat(info.name, ', '), list.\"unique\" \
+            FROM pragma_index_list(?1, ?2) AS list, pragma_index_info(list.name, ?2) AS info \
+            GROUP BY list.name ORDER BY list.name")?;
+        let indexes= stmt.query_map([table,schema],|row| {
+            Ok(format!("{} ({}){}",row.get::<_,String>(0)?,row.get::<_,String>(1)?,
+                       match row.get::<_,bool>(2)?{
+                           true=> " unique",
+                           false=> ""
+                       }))
+        })?.collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(indexes)
+    }
+    fn triggers(&mut self,schema: &str,table: &str)-> AppResult<Vec<String>>{
+        let mut stmt= self.conn.prepare(&format!(
+                "SELECT name FROM {}.sqlite_master \
+                WHERE type = 'trigger' AND tbl_name = ?1 ORDER BY name",
+                self.quote_identifier(schema)))?;
+        let triggers= stmt.query_map([table],|row| row.get::<_,String>(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(triggers)
+    }
+    fn describe_table(&mut self,schema: &str,table: &str)-> AppResult<TableInfo>{
+        let indexes= self.indexes(schema,table)?;
+        let mut stmt= self.conn.prepare(
+            "SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?1, ?2)")?;
+        let columns= stmt.query_map([table,schema],|row| {
+            Ok(ColumnInfo{
+                name: row.get(0)?,
+                kind: row.get(1)?,
+                nullable: !row.get::<_,bool>(2)?,
+                default: row.get(3)?,
+                key: match row.get::<_,i64>(4)?{
+                    0=> String::new(),
+                    _=> String::from("PRI")
+                },
+                comment: String::new()
+            })
+        })?.collect::<rusqlite::Result<Vec<ColumnInfo>>>()?;
+        let mut stmt= self.conn.prepare(
+            "SELECT \"from\", \"table\", \"to\" FROM pragma_foreign_key_list(?1, ?2) \
+            ORDER BY id, seq")?;
+        let foreign_keys= stmt.query_map([table,schema],|row| {
+            Ok(format!("{} -> {}({})",row.get::<_,String>(0)?,row.get::<_,String>(1)?,
+                       row.get::<_,Option<String>>(2)?.unwrap_or_default()))
+        })?.collect::<rusqlite::Result<Vec<String>>>()?;
+        let mut stmt= self.conn.prepare(&format!(
+                "SELECT sql FROM {}.sqlite_master WHERE tbl_name = ?1 AND sql IS NOT NULL \
+                ORDER BY type NOT IN ('table', 'view'), name",
+                self.quote_identifier(schema)))?;
+        let ddl= stmt.query_map([table],|row| row.get::<_,String>(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?
+            .join(";\n");
+        Ok(TableInfo{
+            schema: schema.to_string(),
+            name: table.to_string(),
+            columns,
+            indexes,
+            foreign_keys,
+            ddl: format!("{};",ddl)
+        })
+    }
+    fn quote_identifier(&self,name: &str)-> String{
+        Engine::Sqlite.quote_identifier(name)
+    }
+    fn current_schema(&mut self)-> AppResult<String>{
+        Ok(String::from("main"))
+    }
+}
+
+fn to_value(value: ValueRef)-> Value{
+    match value{
+        ValueRef::Null=> Value::Null,
+        ValueRef::Integer(int)=> Value::Int(int),
+        ValueRef::Real(real)=> Value::Float(real),
+        ValueRef::Text(text)=> Value::Text(String::from_utf8_lossy(text).to_string()),
+        ValueRef::Blob(blob)=> Value::Bytes(blob.to_vec())
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::app::Engine;
+use crate::database::Value;
+
+pub struct Edits{
+    pub engine: Engine,
+    pub table: String,
+    pub columns: Vec<String>,
+    pub key: Vec<usize>,
+    pub original: BTreeMap<usize,Vec<Value>>,
+    pub deleted: BTreeSet<usize>,
+    pub inserted: BTreeSet<usize>
+}
+
+impl Edits{
+    pub fn new(engine: Engine,table: String,columns: Vec<String>,key: Vec<usize>)-> Self{
+        Self{
+            engine,
+            table,
+            columns,
+            key,
+            original: BTreeMap::new(),
+            deleted: BTreeSet::new(),
+            inserted: BTreeSet::new()
+        }
+    }
+    pub fn is_empty(&self)-> bool{
+        self.original.is_empty()&& self.deleted.is_empty()&& self.inserted.is_empty()
+    }
+    pub fn is_changed(&self,row: usize,col: usize,value: &Value)-> bool{
+        match self.original.get(&row){
+            Some(original)=> original.get(col)!= Some(value),
+            None=> self.inserted.contains(&row)&& *value!= Value::Null
+        }
+    }
+    pub fn set(&mut self,items: &mut [Vec<Value>],row: usize,col: usize,value: Value){
+        if !self.inserted.contains(&row){
+            self.original.entry(row).or_insert_with(|| items[row].clone());
+        }
+        items[row][col]= value;
+    }
+    pub fn revert(&mut self,items: &mut [Vec<Value>],row: usize){
+        if let Some(original)= self.original.remove(&row){
+            items[row]= original;
+        }
+        if self.inserted.contains(&row){
+            items[row].fill(Value::Null);
+        }
+        self.deleted.remove(&row);
+    }
+    pub fn statements(&self,items: &[Vec<Value>])-> Vec<String>{
+        let mut statements= Vec::new();
+        for (row,original) in &self.original{
+            if self.deleted.contains(row){
+                continue;
+            }
+            let changes: Vec<String>= items[*row]
+                .iter()
+                .zip(original)
+                .zip(&self.columns)
+                .filter(|((value,original),_)| value!= original)
+                .map(|((value,_),column)| format!("{} = {}",column,value.to_sql(self.engine)))
+                .collect();
+            if !changes.is_empty(){
+                statements.push(format!("UPDATE {} SET {} WHERE {};",
+                                        self.table,changes.join(", "),self.condition(original)));
+            }
+        }
+        for row in self.deleted.difference(&self.inserted){
+            let original= self.original.get(row).unwrap_or(&items[*row]);
+            statements.push(format!("DELETE FROM {} WHERE {};",self.table,self.condition(original)));
+        }
+        for row in self.inserted.difference(&self.deleted){
+            let (columns,values): (Vec<&str>,Vec<String>)= self.columns
+                .iter()
+                .zip(&items[*row])
+                .filter(|(_,value)| **value!= Value::Null)
+                .map(|(column,value)| (column.as_str(),value.to_sql(self.engine)))
+                .unzip();
+            if !columns.is_empty(){
+                statements.push(format!("INSERT INTO {} ({}) VALUES ({});",
+                                        self.table,columns.join(", "),values.join(", ")));
+            }
+        }
+        statements
+    }
+    fn condition(&self,row: &[Value])-> String{
+        self.key
+            .iter()
+            .map(|col| match &row[*col]{
+                Value::Null=> format!("{} IS NULL",self.columns[*col]),
+                value=> format!("{} = {}",self.columns[*col],value.to_sql(self.engine))
+            })
+            .collect::<Vec<String>>()
+            .join(" AND ")
+    }
+}
@@ -1,15 +1,22 @@
 use crate::app::AppResult;
+use crate::database::{Fetch, QueryResult, Value};
 use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
 use std::sync::mpsc;
 use std::thread;
 use std::time::{Duration, Instant};
 
-#[derive(Clone,Copy,Debug)]
+#[derive(Debug)]
 pub enum Event{
     Tick,
     Key(KeyEvent),
     Mouse(MouseEvent),
-    Resize(u16,u16)
+    Resize(u16,u16),
+    QueryResult(usize,QueryResult),
+    QueryRows(usize,Vec<Vec<Value>>,Fetch),
+    QueryFinished(usize,QueryResult),
+    QueryError(usize,String),
+    QueryPaused(usize),
+    QueryDone(usize)
 }
 
 #[allow(dead_code)]
@@ -60,6 +67,9 @@ impl EventHandler {
             handler
         }
     }
+    pub fn sender(&self)-> mpsc::Sender<Event> {
+        self.sender.clone()
+    }
     pub fn next(&self)->AppResult<Event> {
         Ok(self.receiver.recv()?)
     }
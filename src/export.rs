@@ -0,0 +1,171 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+use crate::app::{AppResult, Engine};
+use crate::database::{hex, Value};
+
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub enum Format{
+    Csv,
+    Tsv,
+    Json,
+    Markdown,
+    Sql
+}
+
+impl Format{
+    pub fn from_path(path: &Path)-> Option<Self>{
+        let extension= path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str(){
+            "csv"=> Some(Self::Csv),
+            "tsv"| "tab"=> Some(Self::Tsv),
+            "json"=> Some(Self::Json),
+            "md"| "markdown"=> Some(Self::Markdown),
+            "sql"=> Some(Self::Sql),
+            _=> None
+        }
+    }
+}
+
+pub struct Export<'a>{
+    pub format: Format,
+    pub headers: &'a [String],
+    pub rows: Vec<&'a [Value]>,
+    pub table: String,
+    pub engine: Engine,
+    pub quote: &'a dyn Fn(&str)-> String
+}
+
+impl Export<'_>{
+    pub fn render(&self)-> String{
+        match self.format{
+            Format::Csv=> to_csv(self.headers,&self.rows),
+            Format::Tsv=> to_tsv(self.headers,&self.rows),
+            Format::Json=> to_json(self.headers,&self.rows),
+            Format::Markdown=> to_markdown(self.headers,&self.rows),
+            Format::Sql=> to_sql(self.engine,&self.table,self.headers,&self.rows,self.quote)
+        }
+    }
+    pub fn write(&self,path: &Path)-> AppResult<()>{
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(self.render().as_bytes()))
+            .map_err(|err| format!("{}: {}",path.display(),err).into())
+    }
+}
+
+pub fn to_csv(headers: &[String],rows: &[&[Value]])-> String{
+    let field= |text: &str| match text.is_empty()|| text.contains([',','"','\n','\r']){
+        true=> format!("\"{}\"",text.replace('"',"\"\"")),
+        false=> text.to_string()
+    };
+    let mut out= headers.iter().map(|header| field(header)).collect::<Vec<String>>().join(",");
+    out.push_str("\r\n");
+    for row in rows{
+        let line: Vec<String>= row.iter().map(|value| match value{
+            Value::Null=> String::new(),
+            value=> field(&value.display())
+        }).collect();
+        out.push_str(&line.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+pub fn to_tsv(headers: &[String],rows: &[&[Value]])-> String{
+    let field= |text: &str| text
+        .replace('\\',"\\\\")
+        .replace('\t',"\\t")
+        .replace('\n',"\\n")
+        .replace('\r',"\\r");
+    let mut out= headers.iter().map(|header| field(header)).collect::<Vec<String>>().join("\t");
+    out.push('\n');
+    for row in rows{
+        let line: Vec<String>= row.iter().map(|value| match value{
+            Value::Null=> String::from("\\N"),
+            value=> field(&value.display())
+        }).collect();
+        out.push_str(&line.join("\t"));
+        out.push('\n');
+    }
+    out
+}
+
+pub fn to_json(headers: &[String],rows: &[&[Value]])-> String{
+    let keys: Vec<String>= headers
+        .iter()
+        .map(|header| serde_json::Value::from(header.as_str()).to_string())
+        .collect();
+    let objects: Vec<String>= rows.iter().map(|row| {
+        let fields: Vec<String>= keys
+            .iter()
+            .zip(row.iter())
+            .map(|(key,value)| format!("{}: {}",key,json_value(value)))
+            .collect();
+        format!("  {{{}}}",fields.join(", "))
+    }).collect();
+    match objects.is_empty(){
+        true=> String::from("[]\n"),
+        false=> format!("[\n{}\n]\n",objects.join(",\n"))
+    }
+}
+
+fn json_value(value: &Value)-> serde_json::Value{
+    match value{
+        Value::Null=> serde_json::Value::Null,
+        Value::Bool(value)=> serde_json::Value::from(*value),
+        Value::Int(value)=> serde_json::Value::from(*value),
+        Value::UInt(value)=> serde_json::Value::from(*value),
+        Value::Float(value)=> serde_json::Number::from_f64(*value)
+            .map_or_else(|| serde_json::Value::from(value.to_string()),serde_json::Value::Number),
+        Value::Bytes(bytes)=> serde_json::Value::from(format!("0x{}",hex(bytes))),
+        Value::Decimal(value)| Value::Text(value)| Value::DateTime(value)=> {
+            serde_json::Value::from(value.as_str())
+        }
+    }
+}
+
+pub fn to_markdown(headers: &[String],rows: &[&[Value]])-> String{
+    let field= |text: &str| {
+        let mut out= String::new();
+        for ch in text.replace("\r\n","\n").chars(){
+            match ch{
+                '\\'| '|'| '*'| '_'| '`'| '<'=> {
+                    out.push('\\');
+                    out.push(ch);
+                },
+                '\n'=> out.push_str("<br>"),
+                ch=> out.push(ch)
+            }
+        }
+        out
+    };
+    let line= |cells: Vec<String>| format!("| {} |\n",cells.join(" | "));
+    let mut out= line(headers.iter().map(|header| field(header)).collect());
+    out.push_str(&line((0..headers.len()).map(|index| {
+        match rows.iter().find_map(|row| row.get(index).filter(|value| **value!= Value::Null)){
+            Some(value) if value.is_numeric()=> String::from("---:"),
+            _=> String::from("---")
+        }
+    }).collect()));
+    for row in rows{
+        out.push_str(&line(row.iter().map(|value| match value{
+            Value::Null=> String::from("*NULL*"),
+            value=> field(&value.display())
+        }).collect()));
+    }
+    out
+}
+
+pub fn to_sql(engine: Engine,table: &str,headers: &[String],rows: &[&[Value]],quote: &dyn Fn(&str)-> String)-> String{
+    let columns: Vec<String>= headers.iter().map(|header| quote(header)).collect();
+    let mut out= String::new();
+    for row in rows{
+        let values: Vec<String>= row.iter().map(|value| value.to_sql(engine)).collect();
+        out.push_str(&format!("INSERT INTO {} ({}) VALUES ({});\n",table,columns.join(", "),values.join(", ")));
+    }
+    out
+}
@@ -1,40 +1,233 @@
 #[allow(unused)]
-use crate::app::{App,AppMode,AppResult};
+use crate::app::{App,AppMode,AppResult,Panel};
 use crossterm::event::{KeyCode,KeyEvent,KeyModifiers};
 
 pub fn handle_key_events(
     key_event: KeyEvent,
     app: &mut App)-> AppResult<()>{ 
+    if app.session().running.is_some()&&
+        key_event.code== KeyCode::Char('c')&&
+        key_event.modifiers== KeyModifiers::CONTROL{
+        app.cancel_query();
+        return Ok(());
+    }
     match app.mode {
         AppMode::Normal=> normal_handler(app,key_event)?,
         AppMode::Editor=> editor_handler(app,key_event)?,
         AppMode::Message=> message_handler(app,key_event)?,
         AppMode::Table=> table_handler(app,key_event)?,
-        AppMode::Login=> login_handler(app,key_event)?
+        AppMode::Login=> login_handler(app,key_event)?,
+        AppMode::Profile=> profile_handler(app,key_event)?,
+        AppMode::Browser=> browser_handler(app,key_event)?,
+        AppMode::Inspector=> inspector_handler(app,key_event)?,
+        AppMode::Detail=> detail_handler(app,key_event)?,
+        AppMode::Filter| AppMode::Export| AppMode::Edit=> prompt_handler(app,key_event)?,
+        AppMode::Preview=> preview_handler(app,key_event)?
     }
     Ok(())
 }
 
-fn login_handler(
+fn profile_handler(
     app: &mut App,
     key_event: KeyEvent
     )-> AppResult<()>{
     match key_event.code {
-        KeyCode::Char(ch)=> {
-            if ch== 'q'&& key_event.modifiers== KeyModifiers::CONTROL{
-                app.state= false;
-            }else{
-                app.info.password.push(ch);
+        KeyCode::Char('q') if key_event.modifiers== KeyModifiers::CONTROL=> {
+            app.quit();
+        },
+        KeyCode::Up| KeyCode::Char('k')=> {
+            app.prev_profile();
+        },
+        KeyCode::Down| KeyCode::Char('j')=> {
+            app.next_profile();
+        },
+        KeyCode::Enter=> {
+            app.pick_profile();
+        },
+        KeyCode::Esc=> {
+            app.skip_profiles();
+        },
+        _=>{}
+    }
+    Ok(())
+}
+
+fn browser_handler(
+    app: &mut App,
+    key_event: KeyEvent
+    )-> AppResult<()>{
+    match key_event.code {
+        KeyCode::Esc| KeyCode::Char('q')=> {
+            app.change_mode(AppMode::Normal);
+        },
+        KeyCode::Up| KeyCode::Char('k')=> {
+            app.session().browser.prev();
+        },
+        KeyCode::Down| KeyCode::Char('j')=> {
+            app.session().browser.next();
+        },
+        KeyCode::Right| KeyCode::Char('l')=> {
+            app.session().browse(|browser,db| browser.expand(db,false));
+        },
+        KeyCode::Left| KeyCode::Char('h')=> {
+            app.session().browser.collapse();
+        },
+        KeyCode::Char('r')=> {
+            let session= app.session();
+            match session.browser.selected(){
+                Some(_)=> session.browse(|browser,db| browser.expand(db,true)),
+                None=> session.browse(|browser,db| browser.reload(db))
+            }
+        },
+        KeyCode::Char('R')=> {
+            app.session().browse(|browser,db| browser.reload(db));
+        },
+        KeyCode::Enter=> {
+            app.browser_select();
+        },
+        KeyCode::Char('i')=> {
+            app.inspect_table();
+        },
+        _=>{}
+    }
+    Ok(())
+}
+
+fn inspector_handler(
+    app: &mut App,
+    key_event: KeyEvent
+    )-> AppResult<()>{
+    match key_event.code {
+        KeyCode::Esc| KeyCode::Char('q')=> {
+            app.change_mode(AppMode::Browser);
+        },
+        code=> {
+            if let Some(panel)= &mut app.session().inspector{
+                scroll_panel(panel,code);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn prompt_handler(
+    app: &mut App,
+    key_event: KeyEvent
+    )-> AppResult<()>{
+    match key_event.code {
+        KeyCode::Esc=> {
+            app.change_mode(AppMode::Table);
+        },
+        KeyCode::Enter=> match app.mode{
+            AppMode::Export=> app.apply_export(),
+            AppMode::Edit=> app.apply_edit(),
+            _=> app.apply_filter()
+        },
+        KeyCode::Backspace=> {
+            app.prompt.pop();
+        },
+        KeyCode::Char(ch) if !key_event.modifiers.contains(KeyModifiers::CONTROL)=> {
+            app.prompt.push(ch);
+        },
+        _=>{}
+    }
+    Ok(())
+}
+
+fn detail_handler(
+    app: &mut App,
+    key_event: KeyEvent
+    )-> AppResult<()>{
+    match key_event.code {
+        KeyCode::Esc| KeyCode::Char('q')| KeyCode::Enter=> {
+            app.change_mode(AppMode::Table);
+        },
+        code=> {
+            if let Some(panel)= &mut app.session().detail{
+                scroll_panel(panel,code);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn preview_handler(
+    app: &mut App,
+    key_event: KeyEvent
+    )-> AppResult<()>{
+    match key_event.code {
+        KeyCode::Esc| KeyCode::Char('q')=> {
+            app.change_mode(AppMode::Table);
+        },
+        KeyCode::Enter=> {
+            app.commit_edits();
+        },
+        code=> {
+            if let Some(panel)= &mut app.session().detail{
+                scroll_panel(panel,code);
             }
+        }
+    }
+    Ok(())
+}
+
+fn scroll_panel(panel: &mut Panel,code: KeyCode){
+    let page= panel.size.1.saturating_sub(1).max(1);
+    match code {
+        KeyCode::Up| KeyCode::Char('k')=> {
+            panel.scroll_up(1);
+        },
+        KeyCode::Down| KeyCode::Char('j')=> {
+            panel.scroll_down(1);
+        },
+        KeyCode::PageUp=> {
+            panel.scroll_up(page);
+        },
+        KeyCode::PageDown| KeyCode::Char(' ')=> {
+            panel.scroll_down(page);
+        },
+        KeyCode::Left| KeyCode::Char('h')=> {
+            panel.scroll_left();
+        },
+        KeyCode::Right| KeyCode::Char('l')=> {
+            panel.scroll_right();
+        },
+        _=>{}
+    }
+}
+
+fn login_handler(
+    app: &mut App,
+    key_event: KeyEvent
+    )-> AppResult<()>{
+    match key_event.code {
+        KeyCode::Char('q') if key_event.modifiers== KeyModifiers::CONTROL=> {
+            app.quit();
+        },
+        KeyCode::Char('t') if key_event.modifiers== KeyModifiers::CONTROL=> {
+            app.test_login();
+        },
+        KeyCode::Char(ch) if !key_event.modifiers.contains(KeyModifiers::CONTROL)=> {
+            app.login.enter(ch);
+            app.login.scroll_check();
         },
         KeyCode::Backspace=> {
-            app.info.password.pop();
+            app.login.delete();
+            app.login.scroll_check();
+        },
+        KeyCode::Down| KeyCode::Tab=> {
+            app.login.next_entry();
+            app.login.scroll_check();
+        },
+        KeyCode::Up| KeyCode::BackTab=> {
+            app.login.prev_entry();
+            app.login.scroll_check();
         },
         KeyCode::Esc=> {
-            app.state= false;
+            app.cancel_login();
         },
         KeyCode::Enter=> {
-            app.mode= AppMode::Normal;
+            app.login();
         },
         _=>{}
     }
@@ -53,19 +246,71 @@ fn table_handler(
             app.change_mode(AppMode::Normal);
         },
         KeyCode::Up=> {
-            app.table.prev_row();
+            app.session().results.current().prev_row();
         },
         KeyCode::Left=> {
-            app.table.prev_col();
+            app.session().results.current().prev_col();
         },
         KeyCode::Down=> {
-            app.table.next_row();
+            app.session().results.current().next_row();
         },
         KeyCode::Right=> {
-            app.table.next_col();
+            app.session().results.current().next_col();
+        },
+        KeyCode::Char('+')| KeyCode::Char('>')=> {
+            app.session().results.current().widen_col();
+        },
+        KeyCode::Char('-')| KeyCode::Char('<')=> {
+            app.session().results.current().shrink_col();
+        },
+        KeyCode::Char('=')=> {
+            app.session().results.current().fit_widths();
+        },
+        KeyCode::PageDown=> {
+            app.session().results.current().next_page();
+        },
+        KeyCode::PageUp=> {
+            app.session().results.current().prev_page();
+        },
+        KeyCode::Enter=> {
+            app.open_detail();
+        },
+        KeyCode::Char('s')=> {
+            app.session().results.current().sort_col();
+        },
+        KeyCode::Char('/')=> {
+            app.open_filter();
+        },
+        KeyCode::Char('e')=> {
+            app.open_export();
+        },
+        KeyCode::Char('c') if key_event.modifiers== KeyModifiers::NONE=> {
+            app.open_edit();
+        },
+        KeyCode::Char('N') if key_event.modifiers== KeyModifiers::NONE|| key_event.modifiers== KeyModifiers::SHIFT=> {
+            app.set_null();
+        },
+        KeyCode::Char('d') if key_event.modifiers== KeyModifiers::NONE=> {
+            app.delete_row();
+        },
+        KeyCode::Char('a') if key_event.modifiers== KeyModifiers::NONE=> {
+            app.add_row();
+        },
+        KeyCode::Char('u') if key_event.modifiers== KeyModifiers::NONE=> {
+            app.revert_row();
+        },
+        KeyCode::Char('p') if key_event.modifiers== KeyModifiers::NONE=> {
+            app.preview_edits();
+        },
+        KeyCode::Tab=> {
+            app.session().results.next_tab();
+        },
+        KeyCode::BackTab=> {
+            app.session().results.prev_tab();
         }
         _=>{}
     }
+    app.fetch_more();
     Ok(())
 }
 
@@ -80,16 +325,16 @@ fn message_handler(
             app.change_mode(AppMode::Normal);
         },
         KeyCode::Up=> {
-            app.message.scroll_up();
+            app.session().message.scroll_up();
         },
         KeyCode::Down=> {
-            app.message.scroll_down();
+            app.session().message.scroll_down();
         },
         KeyCode::Left=> {
-            app.message.scroll_left();
+            app.session().message.scroll_left();
         },
         KeyCode::Right=> {
-            app.message.scroll_right();
+            app.session().message.scroll_right();
         },
         _=> {}
     }
@@ -112,10 +357,29 @@ fn normal_handler(
         KeyCode::Char('t')=> {
             app.change_mode(AppMode::Table);
         },
-        KeyCode::Char('c')=> {
-            if key_event.modifiers== KeyModifiers::CONTROL {
-                app.quit();
-            }
+        KeyCode::Char('b')=> {
+            app.open_browser();
+        },
+        KeyCode::Char('c') if key_event.modifiers== KeyModifiers::CONTROL=> {
+            app.quit();
+        },
+        KeyCode::Char('r')=> {
+            app.send_current();
+        },
+        KeyCode::Char('s')=> {
+            app.send_selection();
+        },
+        KeyCode::Char('n')=> {
+            app.new_session();
+        },
+        KeyCode::Char('w')=> {
+            app.close_session();
+        },
+        KeyCode::Tab=> {
+            app.next_session();
+        },
+        KeyCode::BackTab=> {
+            app.prev_session();
         },
         KeyCode::Enter=> {
             app.send_query();
@@ -133,96 +397,66 @@ fn editor_handler(
             app.change_mode(AppMode::Normal);
         },
         KeyCode::Backspace=>{
-            match app.editor.delete(){
+            match app.session().editor.delete(){
                 false=> {
-                    app.editor.cursor_left();
-                    app.editor.scroll_check();
+                    app.session().editor.scroll_check();
                 },
                 true=> {
-                    app.editor.cursor_up();
-                    app.editor.cursor_check(true);
-                    app.editor.scroll_check();
+                    app.session().editor.cursor_up();
+                    app.session().editor.cursor_check(true);
+                    app.session().editor.scroll_check();
                 }
             }
         },
         KeyCode::Left=>{
-            app.editor.cursor_left();
-            app.editor.scroll_check();
+            app.session().editor.cursor_left();
+            app.session().editor.scroll_check();
         },
         KeyCode::Right=>{
-           app.editor.cursor_right();
-           app.editor.scroll_check();
+           app.session().editor.cursor_right();
+           app.session().editor.scroll_check();
         },
         KeyCode::Up=>{
-            app.editor.cursor_up();
-            app.editor.scroll_check();
+            app.session().editor.cursor_up();
+            app.session().editor.scroll_check();
             if key_event.modifiers== KeyModifiers::SHIFT{
-                app.editor.cursor_check(true);
+                app.session().editor.cursor_check(true);
             }else {
-                app.editor.cursor_check(false);
+                app.session().editor.cursor_check(false);
             }
         },
         KeyCode::Down=>{
-            app.editor.cursor_down();
-            app.editor.scroll_check();
+            app.session().editor.cursor_down();
+            app.session().editor.scroll_check();
             if key_event.modifiers== KeyModifiers::SHIFT{
-                app.editor.cursor_check(true);
+                app.session().editor.cursor_check(true);
             }else {
-                app.editor.cursor_check(false);
+                app.session().editor.cursor_check(false);
             }
         },
         KeyCode::Enter=>{
-            app.editor.addline();
-            app.editor.cursor_check(false);
-            app.editor.scroll_check();
+            app.session().editor.addline();
+            app.session().editor.cursor_check(false);
+            app.session().editor.scroll_check();
         },
         KeyCode::Char(ch)=>{
             if ch== 'q'&& key_event.modifiers== KeyModifiers::CONTROL{
                 app.mode= AppMode::Normal;
+            }else if ch== 'v'&& key_event.modifiers== KeyModifiers::CONTROL{
+                app.session().editor.toggle_selection();
             }else {
-                app.editor.enter(ch);
-                app.editor.cursor_right();
-                app.editor.scroll_check();
+                app.session().editor.enter(ch);
+                app.session().editor.cursor_right();
+                app.session().editor.scroll_check();
             }
         },
         KeyCode::Tab=> {
-            app.editor.enter(' ');
-            app.editor.cursor_right();
-            app.editor.scroll_check();
+            app.session().editor.enter(' ');
+            app.session().editor.cursor_right();
+            app.session().editor.scroll_check();
         }
         _=>{}
     }
     Ok(())
 }
 
-
-/*
-fn host_handler(
-    app: &mut App,
-    key_event: KeyEvent)-> AppResult<()>{
-    match key_event.code{
-        KeyCode::Enter=> {
-            app.change_mode(AppMode::Normal);
-        },
-        KeyCode::Esc=> {
-            app.change_mode(AppMode::Normal);
-        }
-        KeyCode::Down=> {
-            app.info.next_entry();
-        },
-        KeyCode::Up=> {
-            app.info.prev_entry();
-        },
-        KeyCode::Char(ch)=> {
-            app.info.enter(ch);
-            app.info.scroll_check();
-        },
-        KeyCode::Backspace=> {
-            app.info.delete();
-            app.info.scroll_check();
-        },
-        _=> {}
-    }
-    Ok(())
-}
-*/
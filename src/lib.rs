@@ -1,9 +1,19 @@
 pub mod app;
 
+pub mod config;
+
+pub mod database;
+
+pub mod edit;
+
 pub mod event;
 
+pub mod export;
+
 pub mod handler;
 
+pub mod sql;
+
 pub mod tui;
 
 pub mod ui;
@@ -8,12 +8,14 @@ use ratatui::Terminal;
 
 fn main()-> AppResult<()>{
     let mut app= App::new(); 
-    if app.info.password.len()== 0{
-        app.mode= AppMode::Login;
+    app.load_config()?;
+    if !matches!(app.mode,AppMode::Profile){
+        app.start();
     }
     let backend= CrosstermBackend::new(io::stderr());
     let terminal= Terminal::new(backend)?;
     let events= EventHandler::new(200);
+    app.sender= Some(events.sender());
     let mut tui= Tui::new(terminal,events);
 
     tui.init()?;
@@ -21,6 +23,12 @@ fn main()-> AppResult<()>{
         tui.draw(&mut app)?;
         match tui.events.next()? {
             Event::Key(key_event)=> handle_key_events(key_event,&mut app)?,
+            Event::QueryResult(id,result)=> app.query_result(id,result),
+            Event::QueryRows(id,rows,fetch)=> app.query_rows(id,rows,fetch),
+            Event::QueryFinished(id,result)=> app.query_finished(id,result),
+            Event::QueryError(id,err)=> app.query_error(id,err),
+            Event::QueryPaused(id)=> app.query_paused(id),
+            Event::QueryDone(id)=> app.query_done(id),
             _=>{}
         }
     }
@@ -0,0 +1,386 @@
+use crate::app::Engine;
+
+#[derive(Debug,Clone,PartialEq)]
+pub struct Statement{
+    pub text: String,
+    pub start: usize,
+    pub end: usize
+}
+
+enum State{
+    Normal,
+    Quote(u8,bool),
+    Dollar(String),
+    LineComment,
+    BlockComment
+}
+
+pub fn split(text: &str,engine: Engine)-> Vec<Statement>{
+    let bytes= text.as_bytes();
+    let mysql= engine== Engine::Mysql;
+    let mut statements: Vec<Statement>= Vec::new();
+    let mut delimiter= String::from(";");
+    let mut state= State::Normal;
+    let mut start= 0;
+    let mut has_code= false;
+    let mut index= 0;
+
+    while index< bytes.len(){
+        let byte= bytes[index];
+        match state{
+            State::Quote(quote,escapes)=> {
+                if byte== b'\\'&& escapes{
+                    index+= 1;
+                }else if byte== quote{
+                    if bytes.get(index+ 1)== Some(&quote){
+                        index+= 1;
+                    }else {
+                        state= State::Normal;
+                    }
+                }
+                index+= 1;
+            },
+            State::Dollar(ref tag)=> {
+                if bytes[index..].starts_with(tag.as_bytes()){
+                    index+= tag.len();
+                    state= State::Normal;
+                }else {
+                    index+= 1;
+                }
+            },
+            State::LineComment=> {
+                if byte== b'\n'{
+                    state= State::Normal;
+                }
+                index+= 1;
+            },
+            State::BlockComment=> {
+                if bytes[index..].starts_with(b"*/"){
+                    state= State::Normal;
+                    index+= 1;
+                }
+                index+= 1;
+            },
+            State::Normal=> {
+                if mysql&& !has_code&& at_line_start(bytes,index){
+                    if let Some((new,next))= delimiter_command(text,index){
+                        delimiter= new;
+                        index= next;
+                        start= next;
+                        continue;
+                    }
+                }
+                if bytes[index..].starts_with(delimiter.as_bytes()){
+                    index+= delimiter.len();
+                    push(&mut statements,text,start,index- delimiter.len(),index,has_code);
+                    start= index;
+                    has_code= false;
+                    continue;
+                }
+                match byte{
+                    b'\''| b'"'| b'`'=> {
+                        let escapes= (mysql&& byte!= b'`')||
+                            (engine== Engine::Postgres&& byte== b'\''&& escape_string(bytes,index));
+                        state= State::Quote(byte,escapes);
+                        has_code= true;
+                    },
+                    b'$' if engine== Engine::Postgres=> {
+                        has_code= true;
+                        if let Some(tag)= dollar_tag(bytes,index){
+                            index+= tag.len();
+                            state= State::Dollar(tag);
+                            continue;
+                        }
+                    },
+                    b'#' if mysql=> state= State::LineComment,
+                    b'-' if bytes[index..].starts_with(b"--")&& (!mysql||
+                        bytes.get(index+ 2).is_none_or(|b| b.is_ascii_whitespace()))=> {
+                        state= State::LineComment;
+                    },
+                    b'/' if bytes.get(index+ 1)== Some(&b'*')=> {
+                        state= State::BlockComment;
+                        index+= 1;
+                    },
+                    _=> {
+                        if !byte.is_ascii_whitespace(){
+                            has_code= true;
+                        }
+                    }
+                }
+                index+= 1;
+            }
+        }
+    }
+    push(&mut statements,text,start,bytes.len(),bytes.len(),has_code);
+    statements
+}
+
+pub fn statement_at(text: &str,offset: usize,engine: Engine)-> Option<Statement>{
+    let statements= split(text,engine);
+    let index= statements
+        .iter()
+        .position(|statement| offset<= statement.end)
+        .unwrap_or(statements.len().saturating_sub(1));
+    statements.into_iter().nth(index)
+}
+
+fn is_word_byte(byte: u8)-> bool{
+    byte.is_ascii_alphanumeric()|| byte== b'_'|| byte== b'$'|| byte>= 0x80
+}
+
+fn escape_string(bytes: &[u8],index: usize)-> bool{
+    index> 0&& bytes[index- 1].eq_ignore_ascii_case(&b'e')&&
+        (index< 2|| !is_word_byte(bytes[index- 2]))
+}
+
+fn dollar_tag(bytes: &[u8],index: usize)-> Option<String>{
+    if index> 0&& is_word_byte(bytes[index- 1]){
+        return None;
+    }
+    let rest= &bytes[index+ 1..];
+    let len= rest.iter().position(|byte| *byte== b'$')?;
+    let tag= &rest[..len];
+    if tag.first().is_some_and(|byte| byte.is_ascii_digit())||
+        !tag.iter().all(|byte| byte.is_ascii_alphanumeric()|| *byte== b'_'|| *byte>= 0x80){
+        return None;
+    }
+    String::from_utf8(bytes[index..index+ len+ 2].to_vec()).ok()
+}
+
+fn push(
+    statements: &mut Vec<Statement>,
+    text: &str,
+    start: usize,
+    body_end: usize,
+    end: usize,
+    has_code: bool){
+    if !has_code{
+        return;
+    }
+    statements.push(Statement{
+        text: text[start..body_end].trim().to_string(),
+        start,
+        end
+    });
+}
+
+fn at_line_start(bytes: &[u8],index: usize)-> bool{
+    bytes[..index]
+        .iter()
+        .rev()
+        .take_while(|b| **b!= b'\n')
+        .all(|b| b.is_ascii_whitespace())
+}
+
+fn delimiter_command(text: &str,index: usize)-> Option<(String,usize)>{
+    let line_end= text[index..]
+        .find('\n')
+        .map_or(text.len(),|end| index+ end);
+    let line= text[index..line_end].trim();
+    let mut words= line.split_whitespace();
+    match (words.next(),words.next(),words.next()){
+        (Some(command),Some(new),None) if command.eq_ignore_ascii_case("delimiter")=> {
+            Some((new.to_string(),(line_end+ 1).min(text.len())))
+        },
+        _=> None
+    }
+}
+
+pub fn source_table(query: &str)-> Option<(Option<String>,String)>{
+    let words: Vec<String>= words(query.trim().trim_end_matches(';'))
+        .into_iter()
+        .filter(|(_,depth)| *depth== 0)
+        .map(|(word,_)| word)
+        .collect();
+    if !words.first()?.eq_ignore_ascii_case("select"){
+        return None;
+    }
+    let from= words.iter().position(|word| word.eq_ignore_ascii_case("from"))?;
+    let mut parts= vec![words.get(from+ 1)?.clone()];
+    let mut next= from+ 2;
+    while words.get(next).map(String::as_str)== Some("."){
+        parts.push(words.get(next+ 1)?.clone());
+        next+= 2;
+    }
+    if parts.len()> 2|| parts.iter().any(|part| !is_identifier(part)){
+        return None;
+    }
+    let mut rest= words[next..].iter().peekable();
+    if rest.next_if(|word| word.eq_ignore_ascii_case("as")).is_some(){
+        rest.next().filter(|word| is_identifier(word))?;
+    }else {
+        rest.next_if(|word| is_identifier(word)&& !is_clause(word));
+    }
+    if rest.peek().is_some_and(|word| !is_clause(word)){
+        return None;
+    }
+    if rest.any(|word| ["union","intersect","except"].iter().any(|set| word.eq_ignore_ascii_case(set))){
+        return None;
+    }
+    let table= unquote(&parts.pop()?);
+    Some((parts.pop().map(|schema| unquote(&schema)),table))
+}
+
+pub fn editable_table(query: &str)-> Option<(Option<String>,String)>{
+    let source= source_table(query)?;
+    let words: Vec<String>= words(query.trim().trim_end_matches(';'))
+        .into_iter()
+        .filter(|(_,depth)| *depth== 0)
+        .map(|(word,_)| word)
+        .collect();
+    let from= words.iter().position(|word| word.eq_ignore_ascii_case("from"))?;
+    if words[from..].iter().any(|word| word.eq_ignore_ascii_case("group")|| word.eq_ignore_ascii_case("having")){
+        return None;
+    }
+    for item in words[1..from].split(|word| word== ","){
+        let mut parts= item.iter();
+        loop{
+            match parts.next().map(String::as_str){
+                Some("*")=> break,
+                Some(part) if is_identifier(part)&& !is_keyword(part)=> {},
+                _=> return None
+            }
+            match parts.next().map(String::as_str){
+                Some(".")=> continue,
+                None=> break,
+                Some(_)=> return None
+            }
+        }
+        if parts.next().is_some(){
+            return None;
+        }
+    }
+    Some(source)
+}
+
+pub fn streamable(query: &str)-> bool{
+    let words= words(query.trim().trim_end_matches(';'));
+    let first= match words.first(){
+        Some((word,_))=> word.to_lowercase(),
+        None=> return false
+    };
+    let top= |names: &[&str]| words
+        .iter()
+        .filter(|(_,depth)| *depth== 0)
+        .any(|(word,_)| names.iter().any(|name| word.eq_ignore_ascii_case(name)));
+    let any= |names: &[&str]| words
+        .iter()
+        .any(|(word,_)| names.iter().any(|name| word.eq_ignore_ascii_case(name)));
+    match first.as_str(){
+        "select"| "values"| "table"=> !top(&["into","for"]),
+        "with"=> !top(&["into","for"])&& !any(&["insert","update","delete","merge"]),
+        _=> false
+    }
+}
+
+pub fn transaction_state(query: &str)-> Option<bool>{
+    let words: Vec<String>= words(query.trim().trim_end_matches(';'))
+        .into_iter()
+        .map(|(word,_)| word.to_lowercase())
+        .collect();
+    let word= |index: usize| words.get(index).map_or("",String::as_str);
+    let chain= words.windows(2).any(|pair| pair[0]== "and"&& pair[1]== "chain");
+    match word(0){
+        "begin"| "start"=> Some(true),
+        "rollback" if word(1)== "to"|| word(2)== "to"=> None,
+        "commit"| "end"| "rollback"| "abort"=> Some(chain),
+        "prepare" if word(1)== "transaction"=> Some(false),
+        _=> None
+    }
+}
+
+fn words(text: &str)-> Vec<(String,usize)>{
+    let chars: Vec<char>= text.chars().collect();
+    let mut words= Vec::new();
+    let mut depth: usize= 0;
+    let mut index= 0;
+    while index< chars.len(){
+        let ch= chars[index];
+        let start= index;
+        match ch{
+            '\''| '"'| '`'| '['=> {
+                let close= match ch{
+                    '['=> ']',
+                    _=> ch
+                };
+                index+= 1;
+                while index< chars.len(){
+                    if chars[index]== close{
+                        if chars.get(index+ 1)== Some(&close)&& close!= ']'{
+                            index+= 1;
+                        }else {
+                            break;
+                        }
+                    }
+                    index+= 1;
+                }
+                index+= 1;
+            },
+            '-' if chars.get(index+ 1)== Some(&'-')=> {
+                while index< chars.len()&& chars[index]!= '\n'{
+                    index+= 1;
+                }
+                continue;
+            },
+            '/' if chars.get(index+ 1)== Some(&'*')=> {
+                index+= 2;
+                while index< chars.len()&& !(chars[index]== '*'&& chars.get(index+ 1)== Some(&'/')){
+                    index+= 1;
+                }
+                index+= 2;
+                continue;
+            },
+            _ if ch.is_whitespace()=> {
+                index+= 1;
+                continue;
+            },
+            _ if ch.is_alphanumeric()|| ch== '_'|| ch== '$'=> {
+                while index< chars.len()&& (chars[index].is_alphanumeric()|| chars[index]== '_'|| chars[index]== '$'){
+                    index+= 1;
+                }
+            },
+            _=> index+= 1
+        }
+        let word: String= chars[start..index.min(chars.len())].iter().collect();
+        if word== ")"{
+            depth= depth.saturating_sub(1);
+        }
+        words.push((word.clone(),depth));
+        if word== "("{
+            depth+= 1;
+        }
+    }
+    words
+}
+
+fn is_identifier(word: &str)-> bool{
+    match word.chars().next(){
+        Some('"')| Some('`')| Some('[')=> true,
+        Some(ch)=> ch.is_alphabetic()|| ch== '_',
+        None=> false
+    }
+}
+
+fn is_keyword(word: &str)-> bool{
+    ["distinct","distinctrow","all","as","top","straight_join","sql_no_cache","sql_calc_found_rows","high_priority"]
+        .iter()
+        .any(|keyword| word.eq_ignore_ascii_case(keyword))
+}
+
+fn is_clause(word: &str)-> bool{
+    ["where","group","order","limit","having","window","offset","fetch","for","union","intersect","except"]
+        .iter()
+        .any(|clause| word.eq_ignore_ascii_case(clause))
+}
+
+fn unquote(word: &str)-> String{
+    match word.chars().next(){
+        Some('[')=> word.trim_start_matches('[').trim_end_matches(']').to_string(),
+        Some(quote @ ('"'| '`'))=> {
+            let inner= word.strip_prefix(quote).unwrap_or(word);
+            let inner= inner.strip_suffix(quote).unwrap_or(inner);
+            inner.replace(&format!("{}{}",quote,quote),&quote.to_string())
+        },
+        _=> word.to_string()
+    }
+}
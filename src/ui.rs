@@ -4,11 +4,13 @@ use ratatui::{
     backend::Backend,
     layout::{*,Constraint::*},
     style::*,
+    text::{Line,Span},
     widgets::*,
     Frame
 };
 
-use crate::app::{App,AppMode};
+use crate::app::{App,AppMode,InfoEntries};
+use crate::database::{Fetch, Value};
 
 pub fn render<B: Backend>(app: &mut App,frame: &mut Frame<'_,B>) {
     let size= frame.size();
@@ -19,9 +21,38 @@ pub fn render<B: Backend>(app: &mut App,frame: &mut Frame<'_,B>) {
             render_login(app, frame, size);
             return;
         },
+        AppMode::Profile=> {
+            render_profiles(app, frame, size);
+            return;
+        },
         _=>{}
     }
 
+    let chunks= Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![
+                     Length(1),
+                     Min(0)
+        ]).split(size);
+    render_sessions(app, frame, chunks[0]);
+
+    let size= match app.mode{
+        AppMode::Browser| AppMode::Inspector=> {
+            let chunks= Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![
+                             Length(chunks[1].width/ 4),
+                             Min(0)
+                ]).split(chunks[1]);
+            render_browser(app, frame, chunks[0]);
+            if matches!(app.mode,AppMode::Inspector){
+                render_inspector(app, frame, chunks[1]);
+                return;
+            }
+            chunks[1]
+        },
+        _=> chunks[1]
+    };
     let main_chunks= Layout::default()
         .direction(Direction::Horizontal)
         .constraints(
@@ -29,7 +60,7 @@ pub fn render<B: Backend>(app: &mut App,frame: &mut Frame<'_,B>) {
             Length(size.width- size.width/ 2),
             Length(size.width/ 2),
             Min(0)
-        ]).split(frame.size());
+        ]).split(size);
 
     let output_chunks= Layout::default()
         .direction(Direction::Vertical)
@@ -43,6 +74,9 @@ pub fn render<B: Backend>(app: &mut App,frame: &mut Frame<'_,B>) {
     render_editor(app, frame, main_chunks[0]);
     render_table(app, frame, output_chunks[0]);
     render_message(app, frame, output_chunks[1]);
+    if matches!(app.mode,AppMode::Detail| AppMode::Preview){
+        render_detail(app, frame, size);
+    }
 }
 
 fn render_background<B: Backend>(frame:&mut Frame<'_,B>,size: Rect){
@@ -52,122 +86,150 @@ fn render_background<B: Backend>(frame:&mut Frame<'_,B>,size: Rect){
                         , size);
 }
 
-fn render_login<B: Backend>(app: &mut App,frame:&mut Frame<'_,B>,size: Rect){
-    let context: String= std::iter::repeat('*')
-        .take(app.info.password.len())
+fn render_sessions<B: Backend>(app: &mut App,frame:&mut Frame<'_,B>,size: Rect){
+    let titles: Vec<String>= app.sessions
+        .iter()
+        .enumerate()
+        .map(|(index,session)| format!("{}: {}",index+ 1,session.title()))
         .collect();
-    frame.render_widget(Paragraph::new(format!(
-            "Password is empty,enter password or skip\r\n\
-            Press \'Enter\' to skip/ensure password\r\n\
-            Press \'Esc/<C-q>\' to quit\r\nPassword: {}_",context)
-            ), size)
+    let tabs= Tabs::new(titles)
+        .select(app.current)
+        .style(Style::default().fg(Color::Gray))
+        .highlight_style(Style::default()
+                         .fg(Color::Rgb(25, 25, 25))
+                         .bg(Color::LightGreen)
+                         );
+    frame.render_widget(tabs,size);
 }
 
-fn render_editor<B: Backend>(app: &mut App,frame :&mut Frame<'_,B>,size: Rect){
-    app.editor.getsize((size.height,size.width));
-
-    let editor= Paragraph::new(app.editor.context.concat())
-        .style(
-            Style::default()
-            .fg(match app.mode{
-                AppMode::Editor=> Color::LightGreen,
-                _=> Color::Gray
-            })
-            )
-        .alignment(Alignment::Left)
-        .scroll(app.editor.scroll)
-        .block(
-            Block::default()
-            .title("Editor")
-            .title_alignment(Alignment::Left)
-            .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
-           );
-    frame.render_widget(editor,size);
-            
-    frame.set_cursor(size.x+ app.editor.cursor_index as u16+ 1- app.editor.scroll.1,
-                     size.y+ app.editor.line_index as u16+ 1- app.editor.scroll.0);
+fn render_browser<B: Backend>(app: &mut App,frame:&mut Frame<'_,B>,size: Rect){
+    let browser= &app.session().browser;
+    let items: Vec<ListItem>= browser.visible()
+        .iter()
+        .map(|(path,node)| {
+            ListItem::new(format!("{}{}","  ".repeat(path.len()- 1),node.label()))
+        })
+        .collect();
+    let list= List::new(items)
+        .block(Block::default()
+               .title("Schema")
+               .title(block::Title::from("<i> inspect, <r> refresh")
+                      .alignment(Alignment::Right))
+               .borders(Borders::ALL)
+               .border_type(BorderType::Rounded))
+        .style(Style::default().fg(Color::LightGreen))
+        .highlight_style(Style::default()
+                         .fg(Color::Rgb(25, 25, 25))
+                         .bg(Color::LightGreen));
+    let mut state= ListState::default();
+    state.select(Some(browser.index));
+    frame.render_stateful_widget(list,size,&mut state);
 }
 
-fn render_message<B: Backend>(app:&mut App,frame:&mut Frame<'_,B>,size: Rect){
-    app.message.get_size((size.width,size.height));
-
-    let message= Paragraph::new({
-        format!("{}",app.message.context.concat())
-        })
-    .block(
-        Block::default()
-        .title("Message")
-        .title_alignment(Alignment::Left)
-        .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
-       )
-    .style(
-        Style::default()
-        .fg(match app.mode{
-            AppMode::Message=> Color::LightGreen,
-            _=> Color::Gray
+fn render_inspector<B: Backend>(app: &mut App,frame:&mut Frame<'_,B>,size: Rect){
+    let panel= match &mut app.session().inspector{
+        Some(panel)=> panel,
+        None=> return
+    };
+    panel.get_size((size.width.saturating_sub(2),size.height.saturating_sub(2)));
+    let text: Vec<Line>= panel.lines
+        .iter()
+        .map(|line| match line.starts_with(' '){
+            true=> Line::from(line.as_str()),
+            false=> Line::styled(line.as_str(),Style::default().fg(Color::LightGreen))
         })
-        )
-    .alignment(Alignment::Left)
-    .scroll(app.message.scroll);
-    frame.render_widget(message,size);
+        .collect();
+    let panel= Paragraph::new(text)
+        .block(Block::default()
+               .title(panel.title.as_str())
+               .title(block::Title::from("<Esc> back")
+                      .alignment(Alignment::Right))
+               .borders(Borders::ALL)
+               .border_type(BorderType::Thick))
+        .style(Style::default().fg(Color::Gray))
+        .scroll(panel.scroll);
+    frame.render_widget(panel,size);
 }
 
-fn render_table<B: Backend>(app:&mut App,frame: &mut Frame<'_,B>,size: Rect){
-    app.table.get_size((size.width,size.height));
-    
-    let col_bg: usize= app.table.scroll.0 as usize;
-    let row_bg: usize= app.table.scroll.1 as usize;
-    let header_cells= app.table.headers[col_bg..]
+fn render_detail<B: Backend>(app: &mut App,frame:&mut Frame<'_,B>,size: Rect){
+    let hint= match app.mode{
+        AppMode::Preview=> "<Enter> commit <Esc> back",
+        _=> "<Esc> close"
+    };
+    let panel= match &mut app.session().detail{
+        Some(panel)=> panel,
+        None=> return
+    };
+    let area= Rect{
+        x: size.x+ size.width/ 10,
+        y: size.y+ size.height/ 10,
+        width: size.width- size.width/ 5,
+        height: size.height- size.height/ 5
+    };
+    panel.get_size((area.width.saturating_sub(2),area.height.saturating_sub(2)));
+    let text: Vec<Line>= panel.lines
         .iter()
-        .map(|h| Cell::from(h.to_string())
-             .style(Style::default()
-                    .fg(Color::Rgb(25, 25, 25))
-                    )
-             );
-    let header= Row::new(header_cells)
-        .height(1)
+        .map(|line| Line::from(line.as_str()))
+        .collect();
+    let popup= Paragraph::new(text)
+        .block(Block::default()
+               .title(panel.title.as_str())
+               .title(block::Title::from(hint)
+                      .alignment(Alignment::Right))
+               .borders(Borders::ALL)
+               .border_type(BorderType::Thick))
         .style(Style::default()
-               .bg(Color::Gray)
-               );
+               .fg(Color::Gray)
+               .bg(Color::Rgb(25, 25, 25)))
+        .wrap(Wrap{trim: false})
+        .scroll(panel.scroll);
+    frame.render_widget(Clear,area);
+    frame.render_widget(popup,area);
+}
 
-    let items= app.table.items[row_bg..].iter().map(|item| {
-        let cells= item[col_bg..].iter().map(|c| Cell::from(c.to_string()));
-        Row::new(cells)
-            .height(1)
-            .style(Style::default()
-                   .fg(Color::Gray)
-                   )
-    });
-    let widths: Vec<Constraint>= vec![
-        Length(size.width/ 3),
-        Length(size.width/ 3),
-        Length(size.width/ 3),
-        Min(0),
-    ];
-    let table= Table::new(items)
-        .header(header)
+fn render_profiles<B: Backend>(app: &mut App,frame:&mut Frame<'_,B>,size: Rect){
+    let chunks= Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![
+                     Length(size.width/ 4),
+                     Length(size.width/ 2),
+                     Min(0)
+        ]).split(size);
+    let chunks= Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![
+                     Length(size.height/ 8),
+                     Length(app.profiles.len() as u16+ 2),
+                     Length(3),
+                     Min(0)
+        ]).split(chunks[1]);
+
+    let items: Vec<ListItem>= app.profiles
+        .iter()
+        .map(|(name,profile)| ListItem::new(format!("{:<16} {}",name,profile.describe())))
+        .collect();
+    let list= List::new(items)
         .block(Block::default()
-               .style(Style::default()
-                      .fg(match app.mode{
-                          AppMode::Table=> Color::LightGreen,
-                          _=> Color::Gray
-                      })
-                      )
+               .title("Profiles")
                .borders(Borders::ALL)
-               .border_type(BorderType::Thick)
-               .title("Table"))
-        .widths(&widths);
-    frame.render_widget(table,size);
+               .border_type(BorderType::Thick))
+        .style(Style::default().fg(Color::Gray))
+        .highlight_style(Style::default().fg(Color::LightGreen))
+        .highlight_symbol("> ");
+    let mut state= ListState::default();
+    state.select(Some(app.profile_index));
+    frame.render_stateful_widget(list,chunks[1],&mut state);
+    frame.render_widget(Paragraph::new(
+            "<Enter> connect with profile, <Esc> use command line options\r\n\
+            <C-q> quit")
+        .style(Style::default().fg(Color::Gray)), chunks[2]);
 }
 
-/*
-fn render_info<B: Backend>(app: &mut App,frame:&mut Frame<'_,B>,size: Rect){
+fn render_login<B: Backend>(app: &mut App,frame:&mut Frame<'_,B>,size: Rect){
     let chunks= Layout::default()
         .direction(Direction::Horizontal)
         .constraints(vec![
-                     Length(size.width/ 3- 2),
+                     Length((size.width/ 3).saturating_sub(2)),
                      Length(size.width- size.width/3 * 2+ 4),
                      Min(0)
         ])
@@ -175,7 +237,7 @@ fn render_info<B: Backend>(app: &mut App,frame:&mut Frame<'_,B>,size: Rect){
     let block= Layout::default()
         .direction(Direction::Vertical)
         .constraints(vec![
-                     Length(size.height/ 8- 1),
+                     Length((size.height/ 8).saturating_sub(1)),
                      Length(17),
                      Min(0)
         ]).split(chunks[1]);
@@ -200,9 +262,10 @@ fn render_info<B: Backend>(app: &mut App,frame:&mut Frame<'_,B>,size: Rect){
                      Length(3),
                      Length(3),
                      Length(3),
+                     Length(1),
                      Min(0)
         ]).split(chunks[1]);
-    app.info.get_length(lines[0].width);
+    app.login.get_length(lines[1].width);
 
     let mut render_line= |context: String, name: &str,select: bool, size: Rect|{
         let new= Paragraph::new(
@@ -220,7 +283,7 @@ fn render_info<B: Backend>(app: &mut App,frame:&mut Frame<'_,B>,size: Rect){
         .alignment(Alignment::Left)
         .scroll(
             match select{
-                true=> (0,app.info.scroll),
+                true=> (0,app.login.scroll),
                 false=> (0,0)
             }
             )
@@ -233,37 +296,274 @@ fn render_info<B: Backend>(app: &mut App,frame:&mut Frame<'_,B>,size: Rect){
             );
         frame.render_widget(new, size);
     };
-    let select: bool= match app.info.current_entry{
-        InfoEntries::USER=> true,
-        _=> false
-    };
-    render_line(app.info.user.clone(),"User:",select,lines[1]);
+    let select: bool= matches!(app.login.current_entry,InfoEntries::USER);
+    render_line(app.login.user.clone(),"User:",select,lines[1]);
 
-    let select: bool= match app.info.current_entry{
-        InfoEntries::PASS=> true,
-        _=> false
-    };
-    render_line(std::iter::repeat('*')
-                .take(app.info.password.len())
-                .collect(),"Pass:",select,lines[2]);
+    let select: bool= matches!(app.login.current_entry,InfoEntries::PASS);
+    render_line("*".repeat(app.login.password.len()),"Pass:",select,lines[2]);
+
+    let select: bool= matches!(app.login.current_entry,InfoEntries::HOST);
+    render_line(app.login.host.clone(),"Host:",select,lines[3]);
+
+    let select: bool= matches!(app.login.current_entry,InfoEntries::DATABASE);
+    render_line(app.login.database.clone(),"Database:",select,lines[4]);
 
-    let select: bool= match app.info.current_entry{
-        InfoEntries::HOST=> true,
-        _=> false
+    let select: bool= matches!(app.login.current_entry,InfoEntries::PORT);
+    render_line(app.login.port.clone(),"Port:",select,lines[5]);
+
+    let mut help= vec![
+        Line::from("<Up/Down> switch field, <Enter> connect"),
+        Line::from(match app.sessions.len(){
+            1=> "<C-t> test connection, <Esc/C-q> quit",
+            _=> "<C-t> test connection, <Esc> close session, <C-q> quit"
+        }),
+    ];
+    match &app.login.notice{
+        Some(Ok(notice))=> help.push(Line::styled(notice.clone(),Style::default().fg(Color::Green))),
+        Some(Err(notice))=> help.push(Line::styled(notice.clone(),Style::default().fg(Color::Red))),
+        None=> {}
+    }
+    frame.render_widget(Paragraph::new(help)
+                        .style(Style::default().fg(Color::Gray))
+                        .wrap(Wrap{trim: true}), lines[7]);
+}
+
+fn render_editor<B: Backend>(app: &mut App,frame :&mut Frame<'_,B>,size: Rect){
+    let color= match app.mode{
+        AppMode::Editor=> Color::LightGreen,
+        _=> Color::Gray
     };
-    render_line(app.info.host.clone(),"Host:",select,lines[3]);
+    let status= app.session().status();
+    let editor= &mut app.session().editor;
+    editor.getsize((size.height,size.width));
 
-    let select: bool= match app.info.current_entry{
-        InfoEntries::DATABASE=> true,
-        _=> false
+    let selection= editor.selection_range();
+    let mut offset= 0;
+    let lines: Vec<Line>= editor.context.iter().map(|line| {
+        let text= line.trim_end_matches('\n');
+        let start= offset;
+        offset+= line.len();
+        match selection{
+            Some((from,to)) if from< start+ text.len()&& to> start=> {
+                let from= from.saturating_sub(start);
+                let to= (to- start).min(text.len());
+                Line::from(vec![
+                           Span::raw(&text[..from]),
+                           Span::styled(&text[from..to],Style::default().bg(Color::DarkGray)),
+                           Span::raw(&text[to..])
+                ])
+            },
+            _=> Line::from(text)
+        }
+    }).collect();
+    let cursor= (size.x+ editor.cursor_index as u16+ 1- editor.scroll.1,
+                 size.y+ editor.line_index as u16+ 1- editor.scroll.0);
+    let editor= Paragraph::new(lines)
+        .style(
+            Style::default()
+            .fg(color)
+            )
+        .alignment(Alignment::Left)
+        .scroll(editor.scroll)
+        .block(
+            Block::default()
+            .title("Editor")
+            .title(block::Title::from(status)
+                   .alignment(Alignment::Right))
+            .title_alignment(Alignment::Left)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+           );
+    frame.render_widget(editor,size);
+            
+    frame.set_cursor(cursor.0,cursor.1);
+}
+
+fn render_message<B: Backend>(app:&mut App,frame:&mut Frame<'_,B>,size: Rect){
+    app.session().message.get_size((size.width,size.height));
+
+    let message= Paragraph::new(app.session().message.context.concat())
+    .block(
+        Block::default()
+        .title("Message")
+        .title_alignment(Alignment::Left)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+       )
+    .style(
+        Style::default()
+        .fg(match app.mode{
+            AppMode::Message=> Color::LightGreen,
+            _=> Color::Gray
+        })
+        )
+    .alignment(Alignment::Left)
+    .scroll(app.session().message.scroll);
+    frame.render_widget(message,size);
+}
+
+fn render_table<B: Backend>(app:&mut App,frame: &mut Frame<'_,B>,size: Rect){
+    let block= Block::default()
+        .style(Style::default()
+               .fg(match app.mode{
+                   AppMode::Table| AppMode::Filter| AppMode::Export| AppMode::Edit=> Color::LightGreen,
+                   _=> Color::Gray
+               })
+               )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Thick)
+        .title("Table");
+    let table= app.session().results.current();
+    let mut state= Vec::new();
+    if let Some((col,ascending))= table.sort{
+        state.push(format!("sorted by {} {}",table.headers[col],match ascending{
+            true=> "asc",
+            false=> "desc"
+        }));
+    }
+    if let Some(filter)= &table.filter{
+        state.push(format!("filter: {}",filter));
+    }
+    if table.pending> 0{
+        state.push(format!("{} pending statements",table.pending));
+    }
+    let block= match state.is_empty(){
+        true=> block,
+        false=> block.title(block::Title::from(state.join(", "))
+                            .alignment(Alignment::Right))
     };
-    render_line(app.info.database.clone(),"Database:",select,lines[4]);
+    let inner= block.inner(size);
+    frame.render_widget(block,size);
 
-    let select: bool= match app.info.current_entry{
-        InfoEntries::PORT=> true,
-        _=> false
+    let prompt= match app.mode{
+        AppMode::Filter=> Some(format!("/{}_",app.prompt)),
+        AppMode::Export=> Some(format!("export to: {}_",app.prompt)),
+        AppMode::Edit=> {
+            let table= app.session().results.current();
+            let header= table.headers.get(table.cursor.1).cloned().unwrap_or_default();
+            Some(format!("{} = {}_",header,app.prompt))
+        },
+        _=> None
     };
-    render_line(app.info.port.clone(),"Port:",select,lines[5]);
+    let chunks= Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![
+                     Length(1),
+                     Min(0),
+                     Length(match prompt{
+                         Some(_)=> 1,
+                         None=> 0
+                     })
+        ]).split(inner);
+    if let Some(prompt)= prompt{
+        frame.render_widget(Paragraph::new(prompt)
+                            .style(Style::default().fg(Color::LightGreen)), chunks[2]);
+    }
+
+    let titles: Vec<String>= app.session().results.tables
+        .iter()
+        .enumerate()
+        .map(|(index,table)| {
+            let count= match table.filter{
+                Some(_)=> format!("{}/{}",table.view.len(),table.items.len()),
+                None=> table.items.len().to_string()
+            };
+            match table.fetch{
+                Fetch::Done=> format!("{}: {} rows",index+ 1,count),
+                Fetch::More=> format!("{}: fetched {} of ?",index+ 1,count),
+                Fetch::Truncated=> format!("{}: {} rows (max rows)",index+ 1,count),
+                Fetch::Stopped=> format!("{}: {} rows (stopped)",index+ 1,count)
+            }
+        })
+        .collect();
+    let tabs= Tabs::new(titles)
+        .select(app.session().results.index)
+        .highlight_style(Style::default()
+                         .fg(Color::Rgb(25, 25, 25))
+                         .bg(Color::Gray)
+                         );
+    frame.render_widget(tabs,chunks[0]);
+
+    let size= chunks[1];
+    let focused= matches!(app.mode,AppMode::Table| AppMode::Detail| AppMode::Edit| AppMode::Preview);
+    let table= app.session().results.current();
+    table.get_size((size.width,size.height));
+    
+    let col_bg: usize= table.scroll.0 as usize;
+    let row_bg: usize= table.scroll.1 as usize;
+    let visible= table.visible_widths();
+    let widths: Vec<Constraint>= visible
+        .iter()
+        .map(|width| Length(*width))
+        .collect();
+    let col_end= col_bg+ widths.len();
+    let cursor= table.cursor;
+    let header_cells= table.headers[col_bg..col_end]
+        .iter()
+        .enumerate()
+        .map(|(index,h)| Cell::from(h.to_string())
+             .style(match col_bg+ index== cursor.1{
+                 true=> Style::default()
+                     .fg(Color::Rgb(25, 25, 25))
+                     .bg(Color::LightGreen),
+                 false=> Style::default()
+                     .fg(Color::Rgb(25, 25, 25))
+             })
+             );
+    let header= Row::new(header_cells)
+        .height(1)
+        .style(Style::default()
+               .bg(Color::Gray)
+               );
 
+    let edits= table.edits.as_ref();
+    let items= table.view.iter().skip(row_bg).take(size.height as usize).enumerate().map(|(row,index)| {
+        let item= &table.items[*index];
+        let selected= focused&& row_bg+ row== cursor.0;
+        let deleted= edits.is_some_and(|edits| edits.deleted.contains(index));
+        let inserted= edits.is_some_and(|edits| edits.inserted.contains(index));
+        let cells= item[col_bg..col_end].iter().enumerate().map(|(col,value)| {
+            let width= visible[col] as usize;
+            let cell= match value{
+                Value::Null=> Cell::from("NULL")
+                    .style(Style::default()
+                           .fg(Color::DarkGray)
+                           .add_modifier(Modifier::ITALIC)),
+                value if value.is_numeric()=> Cell::from(format!("{:>width$}",value.display(),width= width)),
+                value=> Cell::from(value.display())
+            };
+            let cell= match edits.is_some_and(|edits| edits.is_changed(*index,col_bg+ col,value)){
+                true=> cell.style(Style::default().fg(Color::Yellow)),
+                false=> cell
+            };
+            match selected&& col_bg+ col== cursor.1{
+                true=> cell.style(Style::default()
+                                  .fg(Color::Rgb(25, 25, 25))
+                                  .bg(Color::LightGreen)),
+                false=> cell
+            }
+        });
+        let style= match selected{
+            true=> Style::default()
+                .fg(Color::White)
+                .bg(Color::DarkGray),
+            false=> Style::default()
+                .fg(Color::Gray)
+        };
+        let style= match (deleted,inserted){
+            (true,_)=> style
+                .fg(Color::Red)
+                .add_modifier(Modifier::CROSSED_OUT),
+            (false,true)=> style.fg(Color::LightGreen),
+            _=> style
+        };
+        Row::new(cells)
+            .height(1)
+            .style(style)
+    });
+    let table= Table::new(items)
+        .header(header)
+        .widths(&widths);
+    frame.render_widget(table,size);
 }
-*/
+
@@ -0,0 +1,12 @@
+use structopt::StructOpt;
+use sqltui::app::DatabaseInfo;
+use sqltui::database::{self, Database};
+
+pub fn sqlite(statements: &[&str])-> Box<dyn Database>{
+    let info= DatabaseInfo::from_iter(["sqltui","--sqlite",":memory:"]);
+    let mut conn= database::connect(&info).unwrap();
+    for statement in statements{
+        conn.execute(statement).unwrap();
+    }
+    conn
+}
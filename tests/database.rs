@@ -0,0 +1,55 @@
+mod common;
+
+use structopt::StructOpt;
+use sqltui::app::{AppResult, DatabaseInfo};
+use sqltui::database::{self, Sink, Value};
+
+struct Limit{
+    headers: Vec<String>,
+    rows: Vec<Vec<Value>>,
+    max: usize
+}
+
+impl Sink for Limit{
+    fn columns(&mut self,headers: &[String],_types: &[String])-> AppResult<()>{
+        self.headers= headers.to_vec();
+        Ok(())
+    }
+    fn row(&mut self,row: Vec<Value>)-> AppResult<bool>{
+        self.rows.push(row);
+        Ok(self.rows.len()< self.max)
+    }
+}
+
+const SERIES: &str= "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 1000) SELECT i FROM n";
+
+#[test]
+fn sink_can_stop_early(){
+    let mut conn= common::sqlite(&[]);
+    let mut sink= Limit{headers: Vec::new(),rows: Vec::new(),max: 3};
+    let result= conn.query(SERIES,&mut sink).unwrap();
+    assert_eq!(result.headers,vec!["i"]);
+    assert!(result.rows.is_empty());
+    assert_eq!(sink.headers,vec!["i"]);
+    assert_eq!(sink.rows,vec![vec![Value::Int(1)],vec![Value::Int(2)],vec![Value::Int(3)]]);
+}
+
+#[test]
+fn execute_collects_every_row(){
+    let mut conn= common::sqlite(&[]);
+    let result= conn.execute(SERIES).unwrap();
+    assert_eq!(result.rows.len(),1000);
+    assert_eq!(result.rows.last(),Some(&vec![Value::Int(1000)]));
+}
+
+#[test]
+fn accepts_mysql_ssl_modes(){
+    let error= |mode: &str| {
+        let info= DatabaseInfo::from_iter(["sqltui","-h","127.0.0.1","-P","1","-p","x","--ssl-mode",mode]);
+        database::connect(&info).err().map(|err| err.to_string()).unwrap_or_default()
+    };
+    for mode in ["PREFERRED","preferred","Disabled","REQUIRED","verify_ca","VERIFY-IDENTITY"]{
+        assert!(!error(mode).contains("unknown ssl mode"),"{}",mode);
+    }
+    assert!(error("sometimes").contains("unknown ssl mode 'sometimes'"));
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use sqltui::app::Engine;
+use sqltui::database::{self, Database, Value};
+use sqltui::edit::Edits;
+
+fn items()-> Vec<Vec<Value>>{
+    vec![
+        vec![Value::Int(1),Value::Text(String::from("apple")),Value::Float(1.5)],
+        vec![Value::Int(2),Value::Text(String::from("pear")),Value::Null],
+        vec![Value::Int(3),Value::Text(String::from("plum")),Value::Float(0.5)]
+    ]
+}
+
+fn edits(engine: Engine)-> Edits{
+    let columns= ["\"id\"","\"name\"","\"price\""].iter().map(|column| column.to_string()).collect();
+    Edits::new(engine,String::from("\"main\".\"items\""),columns,vec![0])
+}
+
+fn connect()-> Box<dyn Database>{
+    common::sqlite(&["CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT, price REAL)",
+                     "INSERT INTO items VALUES (1, 'apple', 1.5)","INSERT INTO items VALUES (2, 'pear', NULL)",
+                     "INSERT INTO items VALUES (3, 'plum', 0.5)"])
+}
+
+#[test]
+fn no_statements_without_changes(){
+    let mut items= items();
+    let mut edits= edits(Engine::Sqlite);
+    assert!(edits.is_empty());
+    edits.set(&mut items,0,1,Value::Text(String::from("apple")));
+    assert!(edits.statements(&items).is_empty());
+    assert!(!edits.is_changed(0,1,&items[0][1]));
+}
+
+#[test]
+fn generates_update_delete_and_insert(){
+    let mut items= items();
+    let mut edits= edits(Engine::Sqlite);
+    edits.set(&mut items,0,1,Value::Text(String::from("it's")));
+    edits.set(&mut items,0,2,Value::Null);
+    edits.set(&mut items,1,0,Value::Int(20));
+    edits.deleted.insert(2);
+    items.push(vec![Value::Null,Value::Text(String::from("kiwi")),Value::Float(3.25)]);
+    edits.inserted.insert(3);
+    assert!(edits.is_changed(0,1,&items[0][1]));
+    assert!(!edits.is_changed(0,0,&items[0][0]));
+    assert!(edits.is_changed(3,1,&items[3][1]));
+    assert_eq!(edits.statements(&items),vec![
+        "UPDATE \"main\".\"items\" SET \"name\" = 'it''s', \"price\" = NULL WHERE \"id\" = 1;",
+        "UPDATE \"main\".\"items\" SET \"id\" = 20 WHERE \"id\" = 2;",
+        "DELETE FROM \"main\".\"items\" WHERE \"id\" = 3;",
+        "INSERT INTO \"main\".\"items\" (\"name\", \"price\") VALUES ('kiwi', 3.25);"
+    ]);
+}
+
+#[test]
+fn deleted_edits_and_deleted_inserts_collapse(){
+    let mut items= items();
+    let mut edits= edits(Engine::Sqlite);
+    edits.set(&mut items,0,0,Value::Int(10));
+    edits.deleted.insert(0);
+    items.push(vec![Value::Int(4),Value::Null,Value::Null]);
+    edits.inserted.insert(3);
+    edits.deleted.insert(3);
+    assert_eq!(edits.statements(&items),vec!["DELETE FROM \"main\".\"items\" WHERE \"id\" = 1;"]);
+}
+
+#[test]
+fn revert_restores_the_row(){
+    let mut items= items();
+    let mut edits= edits(Engine::Sqlite);
+    edits.set(&mut items,1,1,Value::Text(String::from("quince")));
+    edits.deleted.insert(1);
+    edits.revert(&mut items,1);
+    assert_eq!(items,self::items());
+    assert!(edits.is_empty());
+}
+
+#[test]
+fn literals_follow_the_engine(){
+    let mut items= items();
+    let mut edits= edits(Engine::Mysql);
+    edits.set(&mut items,0,1,Value::Text(String::from("a\\b")));
+    assert_eq!(edits.statements(&items),vec!["UPDATE \"main\".\"items\" SET \"name\" = 'a\\\\b' WHERE \"id\" = 1;"]);
+    assert_eq!(Value::Bytes(vec![0,255]).to_sql(Engine::Postgres),"'\\x00FF'::bytea");
+    assert_eq!(Value::Bytes(vec![0,255]).to_sql(Engine::Sqlite),"X'00FF'");
+}
+
+#[test]
+fn parses_input_like_the_column(){
+    assert_eq!(Value::Int(1).parse_like("42"),Value::Int(42));
+    assert_eq!(Value::Int(1).parse_like("x"),Value::Text(String::from("x")));
+    assert_eq!(Value::Float(1.0).parse_like("nan"),Value::Text(String::from("nan")));
+    assert_eq!(Value::Decimal(String::from("1.0")).parse_like("2.50"),Value::Decimal(String::from("2.50")));
+    assert_eq!(Value::Bool(true).parse_like("f"),Value::Bool(false));
+    assert_eq!(Value::Bytes(Vec::new()).parse_like("0xabcd"),Value::Bytes(vec![0xab,0xcd]));
+    assert_eq!(Value::Bytes(Vec::new()).parse_like("ab"),Value::Bytes(b"ab".to_vec()));
+}
+
+#[test]
+fn commits_in_a_transaction(){
+    let mut conn= connect();
+    let mut items= items();
+    let mut edits= edits(Engine::Sqlite);
+    edits.set(&mut items,0,1,Value::Text(String::from("apricot")));
+    edits.deleted.insert(1);
+    items.push(vec![Value::Int(4),Value::Text(String::from("kiwi")),Value::Null]);
+    edits.inserted.insert(3);
+    database::transaction(conn.as_mut(),&edits.statements(&items)).unwrap();
+    let result= conn.execute("SELECT id, name FROM items ORDER BY id").unwrap();
+    assert_eq!(result.rows,vec![
+        vec![Value::Int(1),Value::Text(String::from("apricot"))],
+        vec![Value::Int(3),Value::Text(String::from("plum"))],
+        vec![Value::Int(4),Value::Text(String::from("kiwi"))]
+    ]);
+}
+
+#[test]
+fn rolls_back_when_a_row_is_gone(){
+    let mut conn= connect();
+    let mut items= items();
+    let mut edits= edits(Engine::Sqlite);
+    edits.set(&mut items,0,1,Value::Text(String::from("apricot")));
+    edits.deleted.insert(1);
+    conn.execute("DELETE FROM items WHERE id = 2").unwrap();
+    let err= database::transaction(conn.as_mut(),&edits.statements(&items)).unwrap_err();
+    assert!(err.to_string().contains("changed 0 rows"));
+    let result= conn.execute("SELECT name FROM items WHERE id = 1").unwrap();
+    assert_eq!(result.rows,vec![vec![Value::Text(String::from("apple"))]]);
+}
@@ -0,0 +1,49 @@
+use sqltui::app::Editor;
+
+fn editor(lines: &[&str])-> Editor{
+    let mut editor= Editor::new();
+    editor.context= lines.iter().map(|line| line.to_string()).collect();
+    editor
+}
+
+#[test]
+fn selects_across_lines(){
+    let mut editor= editor(&["select 1;\n","select 2;\n"]);
+    editor.cursor_index= 7;
+    editor.toggle_selection();
+    editor.line_index= 1;
+    editor.cursor_index= 8;
+    assert_eq!(editor.selected_text().as_deref(),Some("1;\nselect 2"));
+    editor.toggle_selection();
+    assert_eq!(editor.selected_text(),None);
+}
+
+#[test]
+fn selection_stays_on_char_boundaries(){
+    let mut editor= editor(&["é;\n","sélect ü;\n"]);
+    editor.toggle_selection();
+    editor.cursor_index= 1;
+    assert_eq!(editor.selection_range(),Some((0,0)));
+    assert_eq!(editor.selected_text().as_deref(),Some(""));
+    editor.cursor_index= 3;
+    assert_eq!(editor.selected_text().as_deref(),Some("é;"));
+    editor.line_index= 1;
+    editor.cursor_index= 9;
+    assert_eq!(editor.selected_text().as_deref(),Some("é;\nsélect "));
+    assert_eq!(editor.offset(1,2),5);
+}
+
+#[test]
+fn moves_and_deletes_whole_chars(){
+    let mut editor= editor(&["aéb\n"]);
+    editor.cursor_right();
+    editor.cursor_right();
+    assert_eq!(editor.cursor_index,3);
+    editor.enter('ü');
+    editor.cursor_right();
+    assert_eq!(editor.context[0],"aéüb\n");
+    editor.cursor_left();
+    assert_eq!(editor.cursor_index,3);
+    editor.delete();
+    assert_eq!((editor.context[0].as_str(),editor.cursor_index),("aüb\n",1));
+}
@@ -0,0 +1,230 @@
+mod common;
+
+use std::path::Path;
+use sqltui::app::Engine;
+use sqltui::database::Value;
+use sqltui::export::{to_csv, to_json, to_markdown, to_sql, to_tsv, Export, Format};
+use sqltui::sql::split;
+
+fn headers()-> Vec<String>{
+    ["id","name","score","data","note"].iter().map(|header| header.to_string()).collect()
+}
+
+fn rows()-> Vec<Vec<Value>>{
+    vec![
+        vec![Value::Int(1),Value::Text(String::from("plain")),Value::Float(1.5),
+             Value::Bytes(vec![0,255,16]),Value::Null],
+        vec![Value::Int(-2),Value::Text(String::from("comma, \"quote\"\nline\ttab \\ back")),
+             Value::Float(-0.25),Value::Bytes(Vec::new()),Value::Text(String::from("NULL"))],
+        vec![Value::Int(3),Value::Text(String::new()),Value::Null,Value::Null,
+             Value::Text(String::from("it's | *md* _x_ `y` <br>"))]
+    ]
+}
+
+fn expected()-> Vec<Vec<Option<String>>>{
+    rows().iter().map(|row| row.iter().map(|value| match value{
+        Value::Null=> None,
+        value=> Some(value.display())
+    }).collect()).collect()
+}
+
+fn parse_csv(text: &str)-> Vec<Vec<Option<String>>>{
+    let mut records= Vec::new();
+    let mut record= Vec::new();
+    let mut chars= text.chars().peekable();
+    loop{
+        let mut field= String::new();
+        let mut quoted= false;
+        if chars.peek()== Some(&'"'){
+            quoted= true;
+            chars.next();
+            while let Some(ch)= chars.next(){
+                if ch== '"'{
+                    if chars.peek()== Some(&'"'){
+                        chars.next();
+                    }else {
+                        break;
+                    }
+                }
+                field.push(ch);
+            }
+        }
+        while let Some(ch)= chars.next_if(|ch| *ch!= ','&& *ch!= '\r'){
+            field.push(ch);
+        }
+        record.push(match quoted|| !field.is_empty(){
+            true=> Some(field),
+            false=> None
+        });
+        match chars.next(){
+            Some(',')=> continue,
+            Some('\r')=> {
+                assert_eq!(chars.next(),Some('\n'));
+                records.push(std::mem::take(&mut record));
+                if chars.peek().is_none(){
+                    break;
+                }
+            },
+            _=> panic!("unterminated record")
+        }
+    }
+    records
+}
+
+fn parse_tsv(text: &str)-> Vec<Vec<Option<String>>>{
+    text.lines().map(|line| line.split('\t').map(|field| {
+        if field== "\\N"{
+            return None;
+        }
+        let mut out= String::new();
+        let mut chars= field.chars();
+        while let Some(ch)= chars.next(){
+            match ch{
+                '\\'=> out.push(match chars.next(){
+                    Some('t')=> '\t',
+                    Some('n')=> '\n',
+                    Some('r')=> '\r',
+                    Some(ch)=> ch,
+                    None=> panic!("dangling escape")
+                }),
+                ch=> out.push(ch)
+            }
+        }
+        Some(out)
+    }).collect()).collect()
+}
+
+fn parse_markdown(text: &str)-> Vec<Vec<Option<String>>>{
+    text.lines().map(|line| {
+        let line= line.strip_prefix("| ").and_then(|line| line.strip_suffix(" |")).expect("table row");
+        let mut cells= Vec::new();
+        let mut cell= String::new();
+        let mut chars= line.chars();
+        while let Some(ch)= chars.next(){
+            match ch{
+                '\\'=> cell.push(chars.next().expect("dangling escape")),
+                '|'=> cells.push(std::mem::take(&mut cell)),
+                '<' if chars.as_str().starts_with("br>")=> {
+                    chars.nth(2);
+                    cell.push('\n');
+                },
+                ch=> cell.push(ch)
+            }
+        }
+        cells.push(cell);
+        cells.into_iter().map(|cell| {
+            let cell= cell.trim();
+            match cell== "*NULL*"{
+                true=> None,
+                false=> Some(cell.to_string())
+            }
+        }).collect()
+    }).collect()
+}
+
+#[test]
+fn picks_format_from_extension(){
+    assert_eq!(Format::from_path(Path::new("out.CSV")),Some(Format::Csv));
+    assert_eq!(Format::from_path(Path::new("out.tsv")),Some(Format::Tsv));
+    assert_eq!(Format::from_path(Path::new("out.json")),Some(Format::Json));
+    assert_eq!(Format::from_path(Path::new("out.md")),Some(Format::Markdown));
+    assert_eq!(Format::from_path(Path::new("out.sql")),Some(Format::Sql));
+    assert_eq!(Format::from_path(Path::new("out")),None);
+}
+
+#[test]
+fn csv_round_trip(){
+    let rows= rows();
+    let rows: Vec<&[Value]>= rows.iter().map(Vec::as_slice).collect();
+    let mut records= parse_csv(&to_csv(&headers(),&rows));
+    assert_eq!(records.remove(0),headers().into_iter().map(Some).collect::<Vec<_>>());
+    assert_eq!(records,expected());
+}
+
+#[test]
+fn tsv_round_trip(){
+    let rows= rows();
+    let rows: Vec<&[Value]>= rows.iter().map(Vec::as_slice).collect();
+    let mut records= parse_tsv(&to_tsv(&headers(),&rows));
+    assert_eq!(records.remove(0),headers().into_iter().map(Some).collect::<Vec<_>>());
+    assert_eq!(records,expected());
+}
+
+#[test]
+fn json_round_trip(){
+    let rows= rows();
+    let rows: Vec<&[Value]>= rows.iter().map(Vec::as_slice).collect();
+    let parsed: serde_json::Value= serde_json::from_str(&to_json(&headers(),&rows)).unwrap();
+    let objects= parsed.as_array().unwrap();
+    assert_eq!(objects.len(),rows.len());
+    assert_eq!(objects[0]["id"],serde_json::json!(1));
+    assert_eq!(objects[0]["score"],serde_json::json!(1.5));
+    assert_eq!(objects[0]["data"],serde_json::json!("0x00FF10"));
+    assert_eq!(objects[0]["note"],serde_json::Value::Null);
+    assert_eq!(objects[2]["score"],serde_json::Value::Null);
+    for (object,row) in objects.iter().zip(expected()){
+        let keys: Vec<&String>= object.as_object().unwrap().keys().collect();
+        assert_eq!(keys.len(),headers().len());
+        for (header,value) in headers().iter().zip(row){
+            let parsed= match &object[header]{
+                serde_json::Value::Null=> None,
+                serde_json::Value::String(text)=> Some(text.clone()),
+                value=> Some(value.to_string())
+            };
+            assert_eq!(parsed,value);
+        }
+    }
+    assert_eq!(to_json(&headers(),&[]),"[]\n");
+}
+
+#[test]
+fn markdown_round_trip(){
+    let rows= rows();
+    let rows: Vec<&[Value]>= rows.iter().map(Vec::as_slice).collect();
+    let mut records= parse_markdown(&to_markdown(&headers(),&rows));
+    assert_eq!(records.remove(0),headers().into_iter().map(Some).collect::<Vec<_>>());
+    let separator: Vec<Option<String>>= ["---:","---","---:","---","---"]
+        .iter()
+        .map(|cell| Some(cell.to_string()))
+        .collect();
+    assert_eq!(records.remove(0),separator);
+    assert_eq!(records,expected());
+}
+
+#[test]
+fn sql_round_trip(){
+    let mut conn= common::sqlite(&["CREATE TABLE \"odd \"\"t\"\"\" (id INTEGER, name TEXT, score REAL, data BLOB, note TEXT)"]);
+    let rows= rows();
+    let rows: Vec<&[Value]>= rows.iter().map(Vec::as_slice).collect();
+    let quote= |name: &str| conn.quote_identifier(name);
+    let script= to_sql(Engine::Sqlite,&quote("odd \"t\""),&headers(),&rows,&quote);
+    let statements= split(&script,Engine::Sqlite);
+    assert_eq!(statements.len(),rows.len());
+    for statement in statements{
+        conn.execute(&statement.text).unwrap();
+    }
+    let result= conn.execute("SELECT * FROM \"odd \"\"t\"\"\" ORDER BY rowid").unwrap();
+    assert_eq!(result.headers,headers());
+    assert_eq!(result.rows.iter().map(Vec::as_slice).collect::<Vec<_>>(),rows);
+}
+
+#[test]
+fn never_overwrites_a_file(){
+    let rows= rows();
+    let path= std::env::temp_dir().join(format!("sqltui-test-{}.csv",std::process::id()));
+    std::fs::write(&path,"keep").unwrap();
+    let export= Export{
+        format: Format::Csv,
+        headers: &headers(),
+        rows: rows.iter().map(Vec::as_slice).collect(),
+        table: String::from("t"),
+        engine: Engine::Sqlite,
+        quote: &|name: &str| name.to_string()
+    };
+    assert!(export.write(&path).is_err());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(),"keep");
+    std::fs::remove_file(&path).unwrap();
+    export.write(&path).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(),export.render());
+    std::fs::remove_file(&path).unwrap();
+}
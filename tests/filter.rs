@@ -0,0 +1,80 @@
+use sqltui::app::{Filter, Table};
+use sqltui::database::{QueryResult, Value};
+
+fn headers()-> Vec<String>{
+    ["id","name","total"].iter().map(|header| header.to_string()).collect()
+}
+
+fn rows()-> Vec<Vec<Value>>{
+    vec![
+        vec![Value::Int(1),Value::Text(String::from("Smith")),Value::Decimal(String::from("100.50"))],
+        vec![Value::Int(2),Value::Text(String::from("jones")),Value::Null],
+        vec![Value::Int(10),Value::Null,Value::Decimal(String::from("9.5"))],
+        vec![Value::Int(-3),Value::Text(String::from("NULL")),Value::Decimal(String::from("100"))]
+    ]
+}
+
+fn matching(filter: &str)-> Vec<usize>{
+    let filter= Filter::parse(filter,&headers());
+    rows().iter().enumerate().filter(|(_,row)| filter.matches(row)).map(|(index,_)| index).collect()
+}
+
+#[test]
+fn parses_comparisons_and_text(){
+    assert!(matches!(Filter::parse("TOTAL >= 100",&headers()),Filter::Compare(2,ref op,ref value) if op== ">=" && value== "100"));
+    assert!(matches!(Filter::parse("name = 'a b'",&headers()),Filter::Compare(1,ref op,ref value) if op== "=" && value== "a b"));
+    assert!(matches!(Filter::parse("id<>2",&headers()),Filter::Compare(0,ref op,_) if op== "<>"));
+    assert!(matches!(Filter::parse("  Smith ",&headers()),Filter::Text(ref text) if text== "smith"));
+    assert!(matches!(Filter::parse("other = 1",&headers()),Filter::Text(ref text) if text== "other = 1"));
+}
+
+#[test]
+fn text_matches_any_cell(){
+    assert_eq!(matching("SMI"),vec![0]);
+    assert_eq!(matching("10"),vec![0,2,3]);
+    assert_eq!(matching("null"),vec![1,2,3]);
+}
+
+#[test]
+fn numbers_compare_as_numbers(){
+    assert_eq!(matching("id > 2"),vec![2]);
+    assert_eq!(matching("id <= 1"),vec![0,3]);
+    assert_eq!(matching("total >= 100"),vec![0,3]);
+    assert_eq!(matching("total = 100.0"),vec![3]);
+    assert_eq!(matching("total < x"),vec![0,2,3]);
+}
+
+#[test]
+fn text_compares_as_text(){
+    assert_eq!(matching("name = jones"),vec![1]);
+    assert_eq!(matching("name != jones"),vec![0,3]);
+    assert!(matching("name > k").is_empty());
+    assert_eq!(matching("name ~ MIT"),vec![0]);
+}
+
+#[test]
+fn null_only_matches_equality(){
+    assert_eq!(matching("total = NULL"),vec![1]);
+    assert_eq!(matching("name != null"),vec![0,1,3]);
+    assert!(matching("total < null").is_empty());
+    assert_eq!(matching("name = 'NULL'"),vec![2]);
+}
+
+#[test]
+fn sorts_numbers_nulls_and_text(){
+    let mut table= Table::new();
+    table.get_table(QueryResult{headers: headers(),rows: rows(),..Default::default()});
+    let column= |table: &Table,col: usize| table.rows().map(|row| row[col].display()).collect::<Vec<String>>();
+    table.cursor.1= 2;
+    table.sort_col();
+    assert_eq!(column(&table,0),vec!["2","10","-3","1"]);
+    table.sort_col();
+    assert_eq!(column(&table,0),vec!["1","-3","10","2"]);
+    table.sort_col();
+    assert_eq!(column(&table,0),vec!["1","2","10","-3"]);
+    table.cursor.1= 0;
+    table.sort_col();
+    assert_eq!(column(&table,0),vec!["-3","1","2","10"]);
+    table.set_filter("name ~ s");
+    assert_eq!(column(&table,0),vec!["1","2"]);
+}
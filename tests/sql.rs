@@ -0,0 +1,204 @@
+use sqltui::app::Engine;
+use sqltui::sql::split;
+
+fn texts(input: &str)-> Vec<String>{
+    engine_texts(input,Engine::Mysql)
+}
+
+fn engine_texts(input: &str,engine: Engine)-> Vec<String>{
+    split(input,engine).into_iter().map(|statement| statement.text).collect()
+}
+
+#[test]
+fn splits_on_semicolons(){
+    assert_eq!(texts("select 1;\nselect 2;\n"),vec!["select 1","select 2"]);
+}
+
+#[test]
+fn keeps_last_statement_without_delimiter(){
+    assert_eq!(texts("select 1;select 2"),vec!["select 1","select 2"]);
+}
+
+#[test]
+fn skips_empty_statements(){
+    assert_eq!(texts(";;\n select 1 ;\n;\n"),vec!["select 1"]);
+}
+
+#[test]
+fn ignores_semicolon_in_single_quotes(){
+    assert_eq!(texts("select 'a;b'; select 2"),vec!["select 'a;b'","select 2"]);
+}
+
+#[test]
+fn ignores_semicolon_in_double_quotes(){
+    assert_eq!(texts("select \"a;b\"; select 2"),vec!["select \"a;b\"","select 2"]);
+}
+
+#[test]
+fn ignores_semicolon_in_backticks(){
+    assert_eq!(texts("select `a;b` from t; select 2"),vec!["select `a;b` from t","select 2"]);
+}
+
+#[test]
+fn handles_escaped_quotes(){
+    assert_eq!(texts("select 'it''s;'; select 'a\\';b'; select 3"),
+               vec!["select 'it''s;'","select 'a\\';b'","select 3"]);
+}
+
+#[test]
+fn ignores_semicolon_in_dash_comment(){
+    assert_eq!(texts("select 1 -- no; split\n, 2; select 3"),
+               vec!["select 1 -- no; split\n, 2","select 3"]);
+}
+
+#[test]
+fn double_dash_needs_whitespace(){
+    assert_eq!(texts("select 1--1; select 2"),vec!["select 1--1","select 2"]);
+}
+
+#[test]
+fn ignores_semicolon_in_hash_comment(){
+    assert_eq!(texts("select 1 # no; split\n; select 2"),
+               vec!["select 1 # no; split","select 2"]);
+}
+
+#[test]
+fn ignores_semicolon_in_block_comment(){
+    assert_eq!(texts("select /* a; b */ 1; select 2"),
+               vec!["select /* a; b */ 1","select 2"]);
+}
+
+#[test]
+fn skips_comment_only_statements(){
+    assert_eq!(texts("select 1;\n-- trailing note\n/* block */"),vec!["select 1"]);
+}
+
+#[test]
+fn handles_delimiter_blocks(){
+    let input= "DELIMITER //\n\
+                CREATE PROCEDURE p()\n\
+                BEGIN\n  SELECT 1;\n  SELECT 2;\nEND//\n\
+                DELIMITER ;\n\
+                CALL p();\n";
+    assert_eq!(texts(input),vec![
+               "CREATE PROCEDURE p()\nBEGIN\n  SELECT 1;\n  SELECT 2;\nEND",
+               "CALL p()"]);
+}
+
+#[test]
+fn delimiter_is_case_insensitive(){
+    assert_eq!(texts("delimiter $$\nselect 1$$\nselect 2$$"),vec!["select 1","select 2"]);
+}
+
+#[test]
+fn handles_non_ascii_outside_quotes(){
+    assert_eq!(texts("select é from tëst;select 'ü' -- ß\n;"),vec!["select é from tëst","select 'ü' -- ß"]);
+    assert_eq!(texts("delimiter €\nselect 1€select ä€"),vec!["select 1","select ä"]);
+}
+
+#[test]
+fn keeps_postgres_dollar_quotes_together(){
+    let input= "CREATE FUNCTION f() RETURNS int AS $$\nBEGIN\n  PERFORM 1;\n  RETURN 2;\nEND;\n$$ LANGUAGE plpgsql;\n\
+                SELECT $body$ a; b $body$, $1;\nSELECT f();";
+    assert_eq!(engine_texts(input,Engine::Postgres),vec![
+               "CREATE FUNCTION f() RETURNS int AS $$\nBEGIN\n  PERFORM 1;\n  RETURN 2;\nEND;\n$$ LANGUAGE plpgsql",
+               "SELECT $body$ a; b $body$, $1",
+               "SELECT f()"]);
+    assert_eq!(engine_texts("select a$b; select 2",Engine::Postgres),vec!["select a$b","select 2"]);
+}
+
+#[test]
+fn backslash_escapes_only_on_mysql(){
+    let input= "SELECT 'C:\\'; SELECT 2";
+    assert_eq!(engine_texts(input,Engine::Postgres),vec!["SELECT 'C:\\'","SELECT 2"]);
+    assert_eq!(engine_texts(input,Engine::Sqlite),vec!["SELECT 'C:\\'","SELECT 2"]);
+    assert_eq!(texts(input),vec![input]);
+    assert_eq!(engine_texts("SELECT E'it\\'s;'; SELECT 2",Engine::Postgres),vec!["SELECT E'it\\'s;'","SELECT 2"]);
+}
+
+#[test]
+fn hash_is_a_comment_only_on_mysql(){
+    let input= "SELECT 5 # 3; SELECT 2";
+    assert_eq!(engine_texts(input,Engine::Postgres),vec!["SELECT 5 # 3","SELECT 2"]);
+    assert_eq!(texts(input),vec![input]);
+    assert_eq!(engine_texts("select 1--1; select 2",Engine::Sqlite),vec!["select 1--1; select 2"]);
+}
+
+#[test]
+fn reports_statement_ranges(){
+    let input= "select 1;\nselect 2;";
+    let statements= split(input,Engine::Mysql);
+    assert_eq!((statements[0].start,statements[0].end),(0,9));
+    assert_eq!((statements[1].start,statements[1].end),(9,input.len()));
+}
+
+#[test]
+fn finds_source_table(){
+    use sqltui::sql::source_table;
+    assert_eq!(source_table("select * from users"),Some((None,String::from("users"))));
+    assert_eq!(source_table("SELECT id FROM app.users u WHERE id IN (SELECT 1 FROM t) LIMIT 5"),
+               Some((Some(String::from("app")),String::from("users"))));
+    assert_eq!(source_table("select * from \"odd \"\"name\"\" \" as o order by 1"),
+               Some((None,String::from("odd \"name\" "))));
+    assert_eq!(source_table("select (select 1 from a) x from `b`;"),Some((None,String::from("b"))));
+}
+
+#[test]
+fn no_source_table_for_joins_and_subqueries(){
+    use sqltui::sql::source_table;
+    assert_eq!(source_table("select * from a join b on a.id = b.id"),None);
+    assert_eq!(source_table("select * from a, b"),None);
+    assert_eq!(source_table("select * from (select 1) x"),None);
+    assert_eq!(source_table("select 1 from a union select 2 from b"),None);
+    assert_eq!(source_table("update a set x = 1"),None);
+    assert_eq!(source_table("select 1"),None);
+}
+
+#[test]
+fn streams_plain_selects_only(){
+    use sqltui::sql::streamable;
+    assert!(streamable("select * from t;"));
+    assert!(streamable("VALUES (1), (2)"));
+    assert!(streamable("with x as (select 1) select * from x"));
+    assert!(streamable("select substring(v for 2) from t where v = 'for'"));
+    assert!(!streamable("select * from t for update"));
+    assert!(!streamable("select * into copy from t"));
+    assert!(!streamable("with x as (delete from t returning *) select * from x"));
+    assert!(!streamable("insert into t values (1) returning id"));
+    assert!(!streamable("show search_path"));
+}
+
+#[test]
+fn editable_only_for_plain_columns(){
+    use sqltui::sql::editable_table;
+    let notes= Some((None,String::from("notes")));
+    assert_eq!(editable_table("select * from notes where id > 3 order by id limit 10"),notes);
+    assert_eq!(editable_table("SELECT n.id, `note`, notes.* FROM notes n"),notes);
+    assert_eq!(editable_table("SELECT user_id AS id, note FROM notes"),None);
+    assert_eq!(editable_table("SELECT user_id id, note FROM notes"),None);
+    assert_eq!(editable_table("SELECT DISTINCT id, note FROM notes"),None);
+    assert_eq!(editable_table("SELECT id, count(*) FROM notes GROUP BY id"),None);
+    assert_eq!(editable_table("SELECT id FROM notes GROUP BY id"),None);
+    assert_eq!(editable_table("SELECT id FROM notes HAVING id > 1"),None);
+    assert_eq!(editable_table("SELECT id, note || 'x' FROM notes"),None);
+    assert_eq!(editable_table("SELECT id, 1 FROM notes"),None);
+    assert_eq!(editable_table("SELECT max(id) FROM notes"),None);
+}
+
+#[test]
+fn tracks_transaction_statements(){
+    use sqltui::sql::transaction_state;
+    assert_eq!(transaction_state("BEGIN;"),Some(true));
+    assert_eq!(transaction_state("start transaction isolation level serializable"),Some(true));
+    assert_eq!(transaction_state("commit"),Some(false));
+    assert_eq!(transaction_state("END"),Some(false));
+    assert_eq!(transaction_state("abort"),Some(false));
+    assert_eq!(transaction_state("rollback work"),Some(false));
+    assert_eq!(transaction_state("COMMIT AND CHAIN"),Some(true));
+    assert_eq!(transaction_state("rollback and no chain"),Some(false));
+    assert_eq!(transaction_state("ROLLBACK TO SAVEPOINT a"),None);
+    assert_eq!(transaction_state("rollback transaction to a"),None);
+    assert_eq!(transaction_state("PREPARE TRANSACTION 'x'"),Some(false));
+    assert_eq!(transaction_state("prepare q as select 1"),None);
+    assert_eq!(transaction_state("select 'begin'"),None);
+}